@@ -1,17 +1,15 @@
-advent_of_code::solution!(2);
+advent_of_code::solution!(2, variants: {
+    2 => ["string" => part_two_string],
+});
 
-fn split_range_of_ids(input: &str) -> Vec<(u64, u64)> {
+/// Parses `start-end` ranges separated by commas. Returns `None` if any range is malformed.
+fn split_range_of_ids(input: &str) -> Option<Vec<(u64, u64)>> {
     input
         .trim()
         .split(',')
-        .map(|range| range.split_once('-').unwrap())
-        .map(|(a, b)| {
-            (
-                a.parse()
-                    .unwrap_or_else(|e| panic!("Failed to parse '{a}': {e}")),
-                b.parse()
-                    .unwrap_or_else(|e| panic!("Failed to parse '{b}': {e}")),
-            )
+        .map(|range| {
+            let (a, b) = range.split_once('-')?;
+            Some((a.parse().ok()?, b.parse().ok()?))
         })
         .collect()
 }
@@ -32,7 +30,7 @@ fn contains_invalid_sequence_1(sequence: u64) -> bool {
 
 fn solver(input: &str, is_invalid: fn(u64) -> bool) -> Option<u64> {
     let mut sum = 0;
-    for (range_start, range_end) in split_range_of_ids(input) {
+    for (range_start, range_end) in split_range_of_ids(input)? {
         for i in range_start..=range_end {
             if is_invalid(i) {
                 sum += i;
@@ -47,7 +45,6 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 // The original implementation, renamed.
-#[allow(dead_code)]
 fn contains_invalid_sequence_2_string(input: u64) -> bool {
     let s = input.to_string();
     let n = s.len();
@@ -111,21 +108,14 @@ pub fn part_two(input: &str) -> Option<u64> {
     solver(input, contains_invalid_sequence_2)
 }
 
+pub fn part_two_string(input: &str) -> Option<u64> {
+    solver(input, contains_invalid_sequence_2_string)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(1227775554));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4174379265));
-    }
+    use advent_of_code::helper::property::{check, check_equivalent, generate};
 
     #[test]
     fn test_implementations_consistency() {
@@ -135,4 +125,40 @@ mod tests {
             assert_eq!(string_result, math_result, "Mismatch for number {}", i);
         }
     }
+
+    #[test]
+    fn test_implementations_agree_on_generated_ids() {
+        check_equivalent(
+            generate::repeated_digits(1..=6, 2..=3),
+            |&i| contains_invalid_sequence_2_string(i),
+            |&i| contains_invalid_sequence_2(i),
+        );
+        check_equivalent(
+            generate::integers(0..=u64::MAX),
+            |&i| contains_invalid_sequence_2_string(i),
+            |&i| contains_invalid_sequence_2(i),
+        );
+    }
+
+    #[test]
+    fn test_split_range_of_ids_roundtrip() {
+        check(generate::id_ranges(1..=10, 1_000_000_000), |input| {
+            let formatted: Vec<String> = split_range_of_ids(input)
+                .unwrap()
+                .iter()
+                .map(|(start, end)| format!("{start}-{end}"))
+                .collect();
+            assert_eq!(formatted.join(","), *input);
+        });
+    }
+
+    #[test]
+    fn test_split_range_of_ids_never_panics() {
+        check(
+            generate::mutated(generate::id_ranges(1..=10, 1_000_000_000), 3),
+            |input| {
+                let _ = split_range_of_ids(input);
+            },
+        );
+    }
 }
@@ -6,7 +6,8 @@ struct Inventory {
     ingredients: Vec<u64>,
 }
 impl Inventory {
-    fn parse(input: &str) -> Self {
+    /// Parses the fresh ranges, an empty line, then the ingredient ids. Returns `None` if a line is malformed.
+    fn parse(input: &str) -> Option<Self> {
         let mut ranges = Vec::new();
         let mut ingredients = Vec::new();
         let mut parse_ranges = true;
@@ -16,21 +17,21 @@ impl Inventory {
                 continue;
             }
             if parse_ranges {
-                let (start, end) = line.split_once('-').unwrap();
-                ranges.push((start.parse().unwrap(), end.parse().unwrap()));
+                let (start, end) = line.split_once('-')?;
+                ranges.push((start.parse().ok()?, end.parse().ok()?));
             } else {
-                ingredients.push(line.parse().unwrap());
+                ingredients.push(line.parse().ok()?);
             }
         }
-        Inventory {
+        Some(Inventory {
             ranges,
             ingredients,
-        }
+        })
     }
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let inventory = Inventory::parse(input);
+    let inventory = Inventory::parse(input)?;
     let mut fresh_count = 0u64;
     for ingredient in inventory.ingredients {
         for (start, end) in inventory.ranges.iter() {
@@ -44,7 +45,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let inventory = Inventory::parse(input);
+    let inventory = Inventory::parse(input)?;
     let mut ranges = inventory.ranges;
 
     // Sort by the start of the range.
@@ -81,16 +82,51 @@ pub fn part_two(input: &str) -> Option<u64> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::helper::property::{Generator, Rng, check, generate};
+    use std::collections::HashSet;
+
+    /// Fresh ranges, an empty line, then one ingredient id per line.
+    fn inventory_input(max: u64) -> impl Generator<(Vec<(u64, u64)>, Vec<u64>, String)> {
+        let ranges = generate::ranges(1..=8, max);
+        let ingredients = generate::vec_of(0..=8, generate::integers(0..=max));
+        move |rng: &mut Rng| {
+            let (ranges, ingredients) = (ranges.generate(rng), ingredients.generate(rng));
+            let mut input = String::new();
+            for (start, end) in &ranges {
+                input += &format!("{start}-{end}\n");
+            }
+            input += "\n";
+            for id in &ingredients {
+                input += &format!("{id}\n");
+            }
+            (ranges, ingredients, input)
+        }
+    }
+
+    #[test]
+    fn test_inventory_parse_roundtrip() {
+        check(inventory_input(u64::MAX), |(ranges, ingredients, input)| {
+            let inventory = Inventory::parse(input).unwrap();
+            assert_eq!(inventory.ranges, *ranges);
+            assert_eq!(inventory.ingredients, *ingredients);
+        });
+    }
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(3));
+    fn test_inventory_parse_never_panics() {
+        let input = move |rng: &mut Rng| inventory_input(1000).generate(rng).2;
+        check(generate::mutated(input, 3), |input| {
+            let _ = Inventory::parse(input);
+        });
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(14));
+    fn test_parts_match_brute_force() {
+        check(inventory_input(200), |(ranges, ingredients, input)| {
+            let fresh: HashSet<u64> = ranges.iter().flat_map(|&(a, b)| a..=b).collect();
+            let fresh_ingredients = ingredients.iter().filter(|id| fresh.contains(id)).count();
+            assert_eq!(part_one(input), Some(fresh_ingredients as u64));
+            assert_eq!(part_two(input), Some(fresh.len() as u64));
+        });
     }
 }
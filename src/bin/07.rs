@@ -1,7 +1,7 @@
-use std::collections::HashMap;
 use std::collections::HashSet;
 
 use advent_of_code::helper::map2d::Map2D;
+use advent_of_code::helper::memo::GridMemo;
 
 advent_of_code::solution!(7);
 
@@ -66,60 +66,32 @@ pub fn part_two(input: &str) -> Option<u64> {
         .map(|x| (x % map.width(), x / map.width()))
         .unwrap();
 
-    let mut cache = HashMap::new();
-    let split_count = count_splits(starting_position, &map, &mut cache);
+    let mut memo = GridMemo::dense(map.width(), map.height());
+    let split_count = count_splits(starting_position, &map, &mut memo);
 
     // Add +1 as we dont need split count but also the original 'timeline'
     Some(split_count + 1)
 }
 
-fn count_splits(
-    pos: (usize, usize),
-    map: &Map2D<u8>,
-    cache: &mut HashMap<(usize, usize), u64>,
-) -> u64 {
-    if let Some(&cached) = cache.get(&pos) {
-        return cached;
-    }
-
+fn count_splits(pos: (usize, usize), map: &Map2D<u8>, memo: &mut GridMemo<u64>) -> u64 {
     let (x, y) = pos;
     if y >= map.height() {
         return 0;
     }
 
-    let result = match map.get((x, y)) {
-        Some(b'.') => count_splits((x, y + 1), map, cache),
+    memo.get_or_compute(pos, |memo| match map.get((x, y)) {
+        Some(b'.') => count_splits((x, y + 1), map, memo),
         Some(b'^') => {
             let mut timelines = 1;
             if x > 0 {
-                timelines += count_splits((x - 1, y), map, cache);
+                timelines += count_splits((x - 1, y), map, memo);
             }
             if x < map.width() - 1 {
-                timelines += count_splits((x + 1, y), map, cache);
+                timelines += count_splits((x + 1, y), map, memo);
             }
             timelines
         }
-        Some(b'S') => count_splits((x, y + 1), map, cache),
+        Some(b'S') => count_splits((x, y + 1), map, memo),
         _ => 0,
-    };
-
-    cache.insert(pos, result);
-    result
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(21));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(40));
-    }
+    })
 }
@@ -1,4 +1,6 @@
-advent_of_code::solution!(10);
+advent_of_code::solution!(10, variants: {
+    2 => ["elimination" => part_two_elimination],
+});
 
 use std::collections::{HashSet, VecDeque};
 
@@ -75,11 +77,11 @@ struct Machine {
 }
 
 impl Machine {
-    fn from_str(line: &str) -> Machine {
-        match machine_parser::parse_machine(line) {
-            Ok((_, machine)) => machine,
-            Err(e) => panic!("Failed to parse machine '{line}': {e}"),
-        }
+    /// Parses a machine line. Returns `None` if the line is malformed.
+    fn from_str(line: &str) -> Option<Machine> {
+        machine_parser::parse_machine(line)
+            .ok()
+            .map(|(_, machine)| machine)
     }
 
     fn num_lights(&self) -> usize {
@@ -121,7 +123,7 @@ impl Machine {
 
     /// Gemini Autocompleted this lp solver when asked, works as well just a bit slower
     /// Feeling Cooked LVL + 1
-    #[allow(dead_code, clippy::all)]
+    #[allow(clippy::all)]
     fn solve_joltage(&self) -> u64 {
         let num_counters = self.joltages.len();
         let num_buttons = self.buttons.len();
@@ -286,7 +288,7 @@ impl Machine {
         let num_buttons = self.buttons.len();
 
         let mut vars = good_lp::ProblemVariables::new();
-        let presses = vars.add_vector(variable().min(0), num_buttons);
+        let presses = vars.add_vector(variable().integer().min(0), num_buttons);
 
         let objective_expr: good_lp::Expression = presses.iter().sum();
         let mut model = vars.minimise(&objective_expr).using(default_solver);
@@ -306,12 +308,12 @@ impl Machine {
     }
 }
 
-fn parse(input: &str) -> Vec<Machine> {
+fn parse(input: &str) -> Option<Vec<Machine>> {
     input.lines().map(Machine::from_str).collect()
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let machines = parse(input);
+    let machines = parse(input)?;
     println!("Machines: {:?}", machines);
 
     let minimal_moves = machines.iter().map(|m| m.solve_state()).sum();
@@ -319,32 +321,82 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let machines = parse(input);
+    let machines = parse(input)?;
 
     let minimal_moves = machines.iter().map(|m| m.solve_joltage_good_lp()).sum();
     Some(minimal_moves)
 }
 
+pub fn part_two_elimination(input: &str) -> Option<u64> {
+    let machines = parse(input)?;
+
+    let minimal_moves = machines.iter().map(|m| m.solve_joltage()).sum();
+    Some(minimal_moves)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use advent_of_code::helper::property::{Rng, check, check_equivalent, generate};
+
+    /// A machine line whose joltages are reachable, by pressing random buttons a random number of times.
+    fn machine_line(rng: &mut Rng) -> String {
+        let num_counters = rng.range(1..=5) as usize;
+        let num_buttons = num_counters + rng.range(0..=1) as usize;
+
+        let target: String = (0..num_counters)
+            .map(|_| if rng.ratio(1, 2) { '#' } else { '.' })
+            .collect();
+        let buttons: Vec<Vec<usize>> = (0..num_buttons)
+            .map(|_| {
+                let mut button: Vec<usize> =
+                    (0..num_counters).filter(|_| rng.ratio(1, 2)).collect();
+                if button.is_empty() {
+                    button.push(rng.index(num_counters));
+                }
+                button
+            })
+            .collect();
+
+        let mut joltages = vec![0; num_counters];
+        for button in &buttons {
+            let presses = rng.range(0..=20) as usize;
+            for &counter in button {
+                joltages[counter] += presses;
+            }
+        }
+
+        let join = |values: &[usize]| {
+            values
+                .iter()
+                .map(|v| v.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let buttons: Vec<String> = buttons.iter().map(|b| format!("({})", join(b))).collect();
+        format!("[{target}] {} {{{}}}", buttons.join(" "), join(&joltages))
+    }
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(7));
+    fn test_joltage_solvers_agree() {
+        check_equivalent(
+            machine_line,
+            |line| Machine::from_str(line).unwrap().solve_joltage(),
+            |line| Machine::from_str(line).unwrap().solve_joltage_good_lp(),
+        );
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(33));
+    fn test_parser_never_panics_on_malformed_machines() {
+        check(generate::mutated(machine_line, 3), |line| {
+            let _ = Machine::from_str(line);
+        });
     }
 
     #[test]
     fn test_part_two_line_2() {
         let line = "[#.#...#] (0,1,4,5,6) (1,4,6) (1,3,6) (1,2,5) (1,2,3) (4) (1,3,5,6) (0,1,2,4) (2,3,4,5,6) {23,74,43,39,55,46,57}";
-        let machine = Machine::from_str(line);
+        let machine = Machine::from_str(line).unwrap();
         assert_eq!(machine.solve_joltage(), 88);
         assert_eq!(machine.solve_joltage_good_lp(), 88);
     }
@@ -352,7 +404,7 @@ mod tests {
     #[test]
     fn test_part_two_line_4() {
         let line = "[#.#..###.] (2,3,4,5) (3,4,5) (0,1,2,3,5,6) (1,3,7) (0,1,3,7) (0,1,4,5,7,8) (0,1,3,6,7,8) (1,2,3,5,6,8) (0,2,5,6) {59,48,29,42,22,50,38,35,27}";
-        let machine = Machine::from_str(line);
+        let machine = Machine::from_str(line).unwrap();
         assert_eq!(machine.solve_joltage(), 71);
         assert_eq!(machine.solve_joltage_good_lp(), 71);
     }
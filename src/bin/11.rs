@@ -1,5 +1,6 @@
-use petgraph::{algo::all_simple_paths, graph::DiGraph};
-use std::{collections::HashMap, hash::RandomState};
+use advent_of_code::helper::dag::{count_paths, count_paths_via};
+use petgraph::graph::DiGraph;
+use std::collections::HashMap;
 
 advent_of_code::solution!(11);
 
@@ -54,75 +55,19 @@ pub fn part_one(input: &str) -> Option<u64> {
     let p = Problem::from_str(input);
     let start_idx = p.node_idx_from_weight("you").unwrap();
     let end_idx = p.node_idx_from_weight("out").unwrap();
-    let paths =
-        all_simple_paths::<Vec<_>, _, RandomState>(p.graph(), *start_idx, *end_idx, 0, None);
-    Some(paths.count() as u64)
+    Some(count_paths(p.graph(), *start_idx, *end_idx).expect("device graph should be acyclic"))
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
     let p = Problem::from_str(input);
-    let graph = p.graph();
-
-    // Helper function for counting paths in a DAG using memoization with a (from, to) key
-    fn count_paths_dag(
-        graph: &DiGraph<String, ()>,
-        from: petgraph::prelude::NodeIndex,
-        to: petgraph::prelude::NodeIndex,
-        memo: &mut HashMap<(petgraph::prelude::NodeIndex, petgraph::prelude::NodeIndex), u64>,
-    ) -> u64 {
-        if from == to {
-            return 1;
-        }
-        let key = (from, to);
-        if let Some(&count) = memo.get(&key) {
-            return count;
-        }
-
-        let count = graph
-            .neighbors(from)
-            .map(|v| count_paths_dag(graph, v, to, memo))
-            .sum();
-
-        memo.insert(key, count);
-        count
-    }
-
-    let mut memo = HashMap::new();
-    let mut count_paths = |from, to| count_paths_dag(graph, from, to, &mut memo);
 
     let svr_idx = *p.node_idx_from_weight("svr").unwrap();
     let out_idx = *p.node_idx_from_weight("out").unwrap();
     let dac_idx = *p.node_idx_from_weight("dac").unwrap();
     let fft_idx = *p.node_idx_from_weight("fft").unwrap();
 
-    // Case 1: svr -> dac -> fft -> out
-    let path1_count =
-        count_paths(svr_idx, dac_idx) * count_paths(dac_idx, fft_idx) * count_paths(fft_idx, out_idx);
-
-    // Case 2: svr -> fft -> dac -> out
-    let path2_count =
-        count_paths(svr_idx, fft_idx) * count_paths(fft_idx, dac_idx) * count_paths(dac_idx, out_idx);
-
-    Some(path1_count + path2_count)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file_part(
-            "examples", DAY, 1,
-        ));
-        assert_eq!(result, Some(5));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(2));
-    }
+    Some(
+        count_paths_via(p.graph(), svr_idx, out_idx, &[dac_idx, fft_idx])
+            .expect("device graph should be acyclic"),
+    )
 }
@@ -0,0 +1,172 @@
+use std::ops::RangeInclusive;
+
+use crate::helper::map2d::Map2D;
+
+/// Integer types that can be used as compressed coordinates.
+pub trait Coordinate: Copy + Ord {
+    /// The next larger coordinate.
+    fn successor(self) -> Self;
+    /// Number of coordinates in `[self, other)`.
+    fn distance(self, other: Self) -> u64;
+}
+
+macro_rules! impl_coordinate {
+    ($($t:ty),*) => {
+        $(
+            impl Coordinate for $t {
+                fn successor(self) -> Self {
+                    self + 1
+                }
+
+                fn distance(self, other: Self) -> u64 {
+                    other.abs_diff(self) as u64
+                }
+            }
+        )*
+    };
+}
+
+impl_coordinate!(i32, i64, u32, u64, usize);
+
+/// Maps sorted, unique coordinates to dense indices and back.
+///
+/// Consecutive coordinates also split the axis into cells `[values[i], values[i + 1])`,
+/// which allows building grids over coordinates that are too large to store densely.
+#[derive(Debug, Clone)]
+pub struct Compression<T> {
+    values: Vec<T>,
+}
+
+impl<T: Coordinate> Compression<T> {
+    pub fn new(values: impl IntoIterator<Item = T>) -> Self {
+        let mut values: Vec<T> = values.into_iter().collect();
+        values.sort_unstable();
+        values.dedup();
+        Self { values }
+    }
+
+    /// Compresses `values` so that each of them gets a cell of width one.
+    /// The gaps between them become cells of their own.
+    pub fn with_unit_cells(values: impl IntoIterator<Item = T>) -> Self {
+        Self::new(values.into_iter().flat_map(|v| [v, v.successor()]))
+    }
+
+    pub fn values(&self) -> &[T] {
+        &self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// The index of `value`, if it is one of the compressed coordinates.
+    pub fn index_of(&self, value: T) -> Option<usize> {
+        self.values.binary_search(&value).ok()
+    }
+
+    /// The coordinate at `index`.
+    pub fn value(&self, index: usize) -> T {
+        self.values[index]
+    }
+
+    pub fn num_cells(&self) -> usize {
+        self.values.len().saturating_sub(1)
+    }
+
+    /// The cell containing `value`, or `None` if it lies outside of all cells.
+    pub fn cell_of(&self, value: T) -> Option<usize> {
+        let idx = self.values.partition_point(|&v| v <= value);
+        (idx > 0 && idx < self.values.len()).then(|| idx - 1)
+    }
+
+    /// The cells covering `[from, to]`, or `None` if either end lies outside of all cells.
+    pub fn cells_between(&self, from: T, to: T) -> Option<RangeInclusive<usize>> {
+        Some(self.cell_of(from)?..=self.cell_of(to)?)
+    }
+
+    /// The number of coordinates inside of cell `index`.
+    pub fn cell_width(&self, index: usize) -> u64 {
+        self.values[index].distance(self.values[index + 1])
+    }
+}
+
+/// Builds a [`Map2D`] with one tile per pair of cells, calling `f` with the first coordinate of each cell.
+pub fn compressed_grid<X: Coordinate, Y: Coordinate, T>(
+    xs: &Compression<X>,
+    ys: &Compression<Y>,
+    mut f: impl FnMut(X, Y) -> T,
+) -> Map2D<T> {
+    Map2D::from_fn(xs.num_cells(), ys.num_cells(), |(x, y)| {
+        f(xs.value(x), ys.value(y))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compression_indices() {
+        let compression = Compression::new([100u64, 5, 7_000_000_000, 5, 42]);
+        assert_eq!(compression.values(), &[5, 42, 100, 7_000_000_000]);
+        assert_eq!(compression.len(), 4);
+        assert_eq!(compression.index_of(42), Some(1));
+        assert_eq!(compression.index_of(7_000_000_000), Some(3));
+        assert_eq!(compression.index_of(43), None);
+        assert_eq!(compression.value(2), 100);
+    }
+
+    #[test]
+    fn test_compression_cells() {
+        let compression = Compression::new([0i64, 10, 15]);
+        assert_eq!(compression.num_cells(), 2);
+        assert_eq!(compression.cell_of(-1), None);
+        assert_eq!(compression.cell_of(0), Some(0));
+        assert_eq!(compression.cell_of(9), Some(0));
+        assert_eq!(compression.cell_of(10), Some(1));
+        assert_eq!(compression.cell_of(14), Some(1));
+        assert_eq!(compression.cell_of(15), None);
+        assert_eq!(compression.cell_width(0), 10);
+        assert_eq!(compression.cell_width(1), 5);
+        assert_eq!(compression.cells_between(3, 12), Some(0..=1));
+        assert_eq!(compression.cells_between(3, 20), None);
+    }
+
+    #[test]
+    fn test_compression_unit_cells() {
+        let compression = Compression::with_unit_cells([2i64, 9, 5]);
+        assert_eq!(compression.values(), &[2, 3, 5, 6, 9, 10]);
+        let widths: Vec<u64> = (0..compression.num_cells())
+            .map(|i| compression.cell_width(i))
+            .collect();
+        assert_eq!(widths, vec![1, 2, 1, 3, 1]);
+        assert_eq!(widths.iter().sum::<u64>(), 10 - 2);
+    }
+
+    #[test]
+    fn test_compressed_grid_area() {
+        // Occupancy of the rectangle [2, 5] x [1, 3] on a compressed grid.
+        let xs = Compression::with_unit_cells([0u64, 2, 5, 9]);
+        let ys = Compression::with_unit_cells([0u64, 1, 3, 9]);
+        let grid = compressed_grid(&xs, &ys, |x, y| {
+            (2..=5).contains(&x) && (1..=3).contains(&y)
+        });
+
+        assert_eq!(grid.width(), xs.num_cells());
+        assert_eq!(grid.height(), ys.num_cells());
+
+        let mut area = 0;
+        for y in 0..grid.height() {
+            for x in 0..grid.width() {
+                if grid.get((x, y)) == Some(&true) {
+                    area += xs.cell_width(x) * ys.cell_width(y);
+                }
+            }
+        }
+        assert_eq!(area, 4 * 3);
+    }
+}
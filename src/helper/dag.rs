@@ -0,0 +1,169 @@
+use std::{error::Error, fmt::Display};
+
+use petgraph::{
+    algo::toposort,
+    graph::{DiGraph, NodeIndex},
+};
+
+/// Error returned by the DAG utilities when the graph is not acyclic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CycleError {
+    node: NodeIndex,
+}
+
+impl CycleError {
+    /// A node that is part of the detected cycle.
+    pub fn node(&self) -> NodeIndex {
+        self.node
+    }
+}
+
+impl Error for CycleError {}
+
+impl Display for CycleError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "expected a directed acyclic graph, found a cycle through node {}",
+            self.node.index()
+        )
+    }
+}
+
+/// Returns the nodes of `graph` in topological order.
+pub fn topological_order<N, E>(graph: &DiGraph<N, E>) -> Result<Vec<NodeIndex>, CycleError> {
+    toposort(graph, None).map_err(|cycle| CycleError {
+        node: cycle.node_id(),
+    })
+}
+
+/// Counts the distinct paths leading from `from` to `to`.
+pub fn count_paths<N, E>(
+    graph: &DiGraph<N, E>,
+    from: NodeIndex,
+    to: NodeIndex,
+) -> Result<u64, CycleError> {
+    count_paths_via(graph, from, to, &[])
+}
+
+/// Counts the distinct paths leading from `from` to `to` that visit every node in `waypoints`,
+/// in any order.
+///
+/// Every node carries one counter per subset of visited waypoints, so keep `waypoints` small.
+///
+/// # Errors
+/// Returns a [`CycleError`] if `graph` contains a cycle.
+///
+/// # Panics
+/// Panics if more than 15 waypoints are given.
+pub fn count_paths_via<N, E>(
+    graph: &DiGraph<N, E>,
+    from: NodeIndex,
+    to: NodeIndex,
+    waypoints: &[NodeIndex],
+) -> Result<u64, CycleError> {
+    assert!(
+        waypoints.len() < 16,
+        "expected less than 16 waypoints, got {}",
+        waypoints.len()
+    );
+
+    let order = topological_order(graph)?;
+
+    let waypoint_mask = |node: NodeIndex| {
+        waypoints
+            .iter()
+            .enumerate()
+            .filter(|&(_, &waypoint)| waypoint == node)
+            .fold(0, |mask, (i, _)| mask | 1 << i)
+    };
+    let num_masks = 1 << waypoints.len();
+
+    // paths[node][mask] holds the number of paths from `from` to `node` visiting the waypoints in `mask`.
+    let mut paths = vec![vec![0u64; num_masks]; graph.node_count()];
+    paths[from.index()][waypoint_mask(from)] = 1;
+
+    for node in order {
+        if node == to {
+            continue;
+        }
+        for mask in 0..num_masks {
+            let count = paths[node.index()][mask];
+            if count == 0 {
+                continue;
+            }
+            for next in graph.neighbors(node) {
+                paths[next.index()][mask | waypoint_mask(next)] += count;
+            }
+        }
+    }
+
+    Ok(paths[to.index()][num_masks - 1])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// a -> b -> d, a -> c -> d, b -> c
+    fn diamond() -> (DiGraph<char, ()>, [NodeIndex; 4]) {
+        let mut graph = DiGraph::new();
+        let a = graph.add_node('a');
+        let b = graph.add_node('b');
+        let c = graph.add_node('c');
+        let d = graph.add_node('d');
+        graph.extend_with_edges([(a, b), (a, c), (b, c), (b, d), (c, d)]);
+        (graph, [a, b, c, d])
+    }
+
+    #[test]
+    fn test_count_paths() {
+        let (graph, [a, b, c, d]) = diamond();
+        assert_eq!(count_paths(&graph, a, d), Ok(3));
+        assert_eq!(count_paths(&graph, b, d), Ok(2));
+        assert_eq!(count_paths(&graph, c, d), Ok(1));
+        assert_eq!(count_paths(&graph, d, a), Ok(0));
+    }
+
+    #[test]
+    fn test_count_paths_to_self() {
+        let (graph, [a, ..]) = diamond();
+        assert_eq!(count_paths(&graph, a, a), Ok(1));
+    }
+
+    #[test]
+    fn test_count_paths_via_waypoints() {
+        let (graph, [a, b, c, d]) = diamond();
+        assert_eq!(count_paths_via(&graph, a, d, &[b]), Ok(2));
+        assert_eq!(count_paths_via(&graph, a, d, &[c]), Ok(2));
+        assert_eq!(count_paths_via(&graph, a, d, &[b, c]), Ok(1));
+        assert_eq!(count_paths_via(&graph, a, d, &[c, b]), Ok(1));
+        assert_eq!(count_paths_via(&graph, a, d, &[a, d]), Ok(3));
+    }
+
+    #[test]
+    fn test_count_paths_via_unreachable_waypoint() {
+        let (mut graph, [a, _, _, d]) = diamond();
+        let e = graph.add_node('e');
+        assert_eq!(count_paths_via(&graph, a, d, &[e]), Ok(0));
+    }
+
+    #[test]
+    #[should_panic(expected = "expected less than 16 waypoints, got 16")]
+    fn test_count_paths_via_too_many_waypoints() {
+        let (graph, [a, _, _, d]) = diamond();
+        let _ = count_paths_via(&graph, a, d, &[a; 16]);
+    }
+
+    #[test]
+    fn test_detects_cycles() {
+        let (mut graph, [a, b, c, d]) = diamond();
+        graph.add_edge(d, b, ());
+        let err = count_paths(&graph, a, d).unwrap_err();
+        assert!([b, c, d].contains(&err.node()));
+        assert!(
+            err.to_string()
+                .starts_with("expected a directed acyclic graph")
+        );
+    }
+}
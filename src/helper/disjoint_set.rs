@@ -0,0 +1,90 @@
+/// Union-find over the elements `0..n`, with path compression and union by size.
+#[derive(Debug, Clone)]
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    size: Vec<usize>,
+}
+
+impl DisjointSet {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            size: vec![1; n],
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.parent.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.parent.is_empty()
+    }
+
+    /// Returns the representative of the set containing `i`.
+    pub fn find(&mut self, i: usize) -> usize {
+        let mut root = i;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+
+        let mut current = i;
+        while self.parent[current] != root {
+            let next = self.parent[current];
+            self.parent[current] = root;
+            current = next;
+        }
+        root
+    }
+
+    /// Merges the sets containing `i` and `j`. Returns `false` if they already were the same set.
+    pub fn union(&mut self, i: usize, j: usize) -> bool {
+        let (mut root_i, mut root_j) = (self.find(i), self.find(j));
+        if root_i == root_j {
+            return false;
+        }
+        if self.size[root_i] < self.size[root_j] {
+            std::mem::swap(&mut root_i, &mut root_j);
+        }
+        self.parent[root_j] = root_i;
+        self.size[root_i] += self.size[root_j];
+        true
+    }
+
+    /// The number of elements in the set containing `i`.
+    pub fn size_of(&mut self, i: usize) -> usize {
+        let root = self.find(i);
+        self.size[root]
+    }
+
+    /// The sizes of all disjoint sets, in no particular order.
+    pub fn set_sizes(&self) -> Vec<usize> {
+        (0..self.len())
+            .filter(|&i| self.parent[i] == i)
+            .map(|i| self.size[i])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disjoint_set() {
+        let mut sets = DisjointSet::new(6);
+        assert!(sets.union(0, 1));
+        assert!(sets.union(2, 3));
+        assert!(sets.union(1, 3));
+        assert!(!sets.union(0, 2));
+
+        assert_eq!(sets.find(0), sets.find(3));
+        assert_ne!(sets.find(0), sets.find(4));
+        assert_eq!(sets.size_of(2), 4);
+        assert_eq!(sets.size_of(5), 1);
+
+        let mut sizes = sets.set_sizes();
+        sizes.sort_unstable();
+        assert_eq!(sizes, vec![1, 1, 4]);
+    }
+}
@@ -0,0 +1,320 @@
+use crate::helper::compress::Compression;
+
+/// Integer geometry on the plane. All predicates treat boundaries as inclusive.
+pub type Point = (i64, i64);
+
+/// Twice the signed area of the triangle `a`, `b`, `c`. Positive for a counter-clockwise turn.
+fn cross(a: Point, b: Point, c: Point) -> i128 {
+    let (abx, aby) = (b.0 as i128 - a.0 as i128, b.1 as i128 - a.1 as i128);
+    let (acx, acy) = (c.0 as i128 - a.0 as i128, c.1 as i128 - a.1 as i128);
+    abx * acy - aby * acx
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Segment {
+    pub a: Point,
+    pub b: Point,
+}
+
+impl Segment {
+    pub fn new(a: Point, b: Point) -> Self {
+        Self { a, b }
+    }
+
+    fn in_bounding_box(&self, p: Point) -> bool {
+        p.0 >= self.a.0.min(self.b.0)
+            && p.0 <= self.a.0.max(self.b.0)
+            && p.1 >= self.a.1.min(self.b.1)
+            && p.1 <= self.a.1.max(self.b.1)
+    }
+
+    /// Whether `p` lies on the segment, including its end points.
+    pub fn contains(&self, p: Point) -> bool {
+        cross(self.a, self.b, p) == 0 && self.in_bounding_box(p)
+    }
+
+    /// Whether the segments share at least one point.
+    pub fn intersects(&self, other: &Segment) -> bool {
+        let d1 = cross(other.a, other.b, self.a).signum();
+        let d2 = cross(other.a, other.b, self.b).signum();
+        let d3 = cross(self.a, self.b, other.a).signum();
+        let d4 = cross(self.a, self.b, other.b).signum();
+
+        if d1 * d2 < 0 && d3 * d4 < 0 {
+            return true;
+        }
+
+        other.contains(self.a)
+            || other.contains(self.b)
+            || self.contains(other.a)
+            || self.contains(other.b)
+    }
+}
+
+/// A simple polygon given by its vertices in drawing order. The last vertex connects to the first.
+#[derive(Debug, Clone)]
+pub struct Polygon {
+    vertices: Vec<Point>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<Point>) -> Self {
+        Self { vertices }
+    }
+
+    pub fn vertices(&self) -> &[Point] {
+        &self.vertices
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = Segment> + '_ {
+        let n = self.vertices.len();
+        (0..n).map(move |i| Segment::new(self.vertices[i], self.vertices[(i + 1) % n]))
+    }
+
+    /// Whether every edge is either horizontal or vertical.
+    pub fn is_rectilinear(&self) -> bool {
+        self.edges().all(|e| e.a.0 == e.b.0 || e.a.1 == e.b.1)
+    }
+
+    pub fn is_on_boundary(&self, p: Point) -> bool {
+        self.edges().any(|e| e.contains(p))
+    }
+
+    /// Whether `p` lies inside of the polygon or on its boundary.
+    pub fn contains(&self, p: Point) -> bool {
+        if self.is_on_boundary(p) {
+            return true;
+        }
+
+        // Cast a ray to the right of `p` and count the edges it crosses.
+        let mut inside = false;
+        for Segment { a, b } in self.edges() {
+            if (a.1 > p.1) != (b.1 > p.1) {
+                // `p` is left of the crossing iff it is left of the edge when walking it upwards.
+                let side = cross(a, b, p);
+                if (side > 0) == (b.1 > a.1) {
+                    inside = !inside;
+                }
+            }
+        }
+        inside
+    }
+}
+
+/// Precomputed interior of a rectilinear [`Polygon`] for constant time containment queries.
+///
+/// The plane is split along every vertex coordinate `v` into the cells `[v, v + 1)` and the gaps between them.
+/// Inside each cell all points behave the same, so the polygon is evaluated once per cell
+/// and a 2D prefix sum over the cells outside answers rectangle queries.
+#[derive(Debug, Clone)]
+pub struct InteriorGrid {
+    xs: Compression<i64>,
+    ys: Compression<i64>,
+    outside_prefix: Vec<u32>,
+}
+
+impl InteriorGrid {
+    pub fn new(polygon: &Polygon) -> Self {
+        assert!(
+            polygon.is_rectilinear(),
+            "InteriorGrid requires a rectilinear polygon"
+        );
+
+        let xs = Compression::with_unit_cells(polygon.vertices().iter().map(|p| p.0));
+        let ys = Compression::with_unit_cells(polygon.vertices().iter().map(|p| p.1));
+
+        let width = xs.num_cells();
+        let height = ys.num_cells();
+        let column_of = |x: i64| xs.index_of(x).unwrap();
+
+        let mut outside_prefix = vec![0u32; (width + 1) * (height + 1)];
+        let mut boundary = vec![false; width];
+        let mut crossings = Vec::new();
+
+        for (row, &y) in ys.values().iter().take(height).enumerate() {
+            boundary.fill(false);
+            crossings.clear();
+
+            for Segment { a, b } in polygon.edges() {
+                if a.1 == b.1 {
+                    if a.1 == y {
+                        let (from, to) = (a.0.min(b.0), a.0.max(b.0));
+                        boundary[column_of(from)..=column_of(to)].fill(true);
+                    }
+                } else {
+                    if y >= a.1.min(b.1) && y <= a.1.max(b.1) {
+                        boundary[column_of(a.0)] = true;
+                    }
+                    if (a.1 > y) != (b.1 > y) {
+                        crossings.push(a.0);
+                    }
+                }
+            }
+            crossings.sort_unstable();
+
+            // Walk the row from left to right, counting the crossings to the right of each cell.
+            let mut passed = 0;
+            let mut row_outside = 0;
+            for (col, &x) in xs.values().iter().take(width).enumerate() {
+                while passed < crossings.len() && crossings[passed] <= x {
+                    passed += 1;
+                }
+                let inside = boundary[col] || (crossings.len() - passed) % 2 == 1;
+                if !inside {
+                    row_outside += 1;
+                }
+                outside_prefix[(row + 1) * (width + 1) + col + 1] =
+                    outside_prefix[row * (width + 1) + col + 1] + row_outside;
+            }
+        }
+
+        Self {
+            xs,
+            ys,
+            outside_prefix,
+        }
+    }
+
+    /// Whether every point of the axis aligned rectangle spanned by `min` and `max` lies inside of the polygon.
+    pub fn contains_rect(&self, min: Point, max: Point) -> bool {
+        let (Some(cols), Some(rows)) = (
+            self.xs.cells_between(min.0, max.0),
+            self.ys.cells_between(min.1, max.1),
+        ) else {
+            return false;
+        };
+        let (x1, x2, y1, y2) = (*cols.start(), *cols.end(), *rows.start(), *rows.end());
+
+        let stride = self.xs.num_cells() + 1;
+        let at = |x: usize, y: usize| self.outside_prefix[y * stride + x] as i64;
+        let outside = at(x2 + 1, y2 + 1) - at(x1, y2 + 1) - at(x2 + 1, y1) + at(x1, y1);
+        outside == 0
+    }
+
+    /// Whether `p` lies inside of the polygon or on its boundary.
+    pub fn contains(&self, p: Point) -> bool {
+        self.contains_rect(p, p)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example_polygon() -> Polygon {
+        Polygon::new(vec![
+            (7, 1),
+            (11, 1),
+            (11, 7),
+            (9, 7),
+            (9, 5),
+            (2, 5),
+            (2, 3),
+            (7, 3),
+        ])
+    }
+
+    /// A "U" shape with a notch that is only one tile wide.
+    fn u_polygon() -> Polygon {
+        Polygon::new(vec![
+            (0, 0),
+            (10, 0),
+            (10, 10),
+            (6, 10),
+            (6, 2),
+            (4, 2),
+            (4, 10),
+            (0, 10),
+        ])
+    }
+
+    #[test]
+    fn test_segment_contains() {
+        let segment = Segment::new((0, 0), (4, 2));
+        assert!(segment.contains((0, 0)));
+        assert!(segment.contains((2, 1)));
+        assert!(segment.contains((4, 2)));
+        assert!(!segment.contains((6, 3)));
+        assert!(!segment.contains((1, 1)));
+    }
+
+    #[test]
+    fn test_segment_intersects() {
+        let horizontal = Segment::new((0, 0), (4, 0));
+        assert!(horizontal.intersects(&Segment::new((2, -2), (2, 2))));
+        assert!(horizontal.intersects(&Segment::new((4, 0), (4, 5))));
+        assert!(horizontal.intersects(&Segment::new((3, 0), (8, 0))));
+        assert!(!horizontal.intersects(&Segment::new((5, 0), (8, 0))));
+        assert!(!horizontal.intersects(&Segment::new((2, 1), (2, 5))));
+        assert!(Segment::new((0, 0), (4, 4)).intersects(&Segment::new((0, 4), (4, 0))));
+        assert!(!Segment::new((0, 0), (4, 4)).intersects(&Segment::new((1, 0), (5, 4))));
+    }
+
+    #[test]
+    fn test_polygon_contains() {
+        let polygon = example_polygon();
+        assert!(polygon.is_rectilinear());
+        assert!(polygon.contains((7, 1)));
+        assert!(polygon.contains((9, 3)));
+        assert!(polygon.contains((2, 4)));
+        assert!(polygon.contains((10, 6)));
+        assert!(!polygon.contains((2, 2)));
+        assert!(!polygon.contains((5, 6)));
+        assert!(!polygon.contains((12, 4)));
+        assert!(!polygon.contains((-1, 3)));
+    }
+
+    #[test]
+    fn test_polygon_contains_triangle() {
+        let polygon = Polygon::new(vec![(0, 0), (6, 0), (0, 6)]);
+        assert!(!polygon.is_rectilinear());
+        assert!(polygon.contains((3, 3)));
+        assert!(polygon.contains((1, 1)));
+        assert!(!polygon.contains((4, 3)));
+        assert!(!polygon.contains((-1, 0)));
+    }
+
+    fn assert_matches_brute_force(polygon: &Polygon) {
+        let grid = InteriorGrid::new(polygon);
+        let xs = polygon.vertices().iter().map(|p| p.0);
+        let ys = polygon.vertices().iter().map(|p| p.1);
+        let (min_x, max_x) = (xs.clone().min().unwrap() - 1, xs.max().unwrap() + 1);
+        let (min_y, max_y) = (ys.clone().min().unwrap() - 1, ys.max().unwrap() + 1);
+
+        for x in min_x..=max_x {
+            for y in min_y..=max_y {
+                assert_eq!(
+                    grid.contains((x, y)),
+                    polygon.contains((x, y)),
+                    "({x}, {y})"
+                );
+            }
+        }
+
+        for &(x1, y1) in polygon.vertices() {
+            for &(x2, y2) in polygon.vertices() {
+                let min = (x1.min(x2), y1.min(y2));
+                let max = (x1.max(x2), y1.max(y2));
+                let expected =
+                    (min.0..=max.0).all(|x| (min.1..=max.1).all(|y| polygon.contains((x, y))));
+                assert_eq!(grid.contains_rect(min, max), expected, "{min:?} {max:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_interior_grid_matches_brute_force() {
+        assert_matches_brute_force(&example_polygon());
+        assert_matches_brute_force(&u_polygon());
+    }
+
+    #[test]
+    fn test_interior_grid_rects() {
+        let grid = InteriorGrid::new(&u_polygon());
+        assert!(grid.contains_rect((0, 0), (10, 2)));
+        assert!(grid.contains_rect((0, 0), (4, 10)));
+        assert!(!grid.contains_rect((0, 0), (10, 3)));
+        assert!(!grid.contains_rect((-1, 0), (1, 1)));
+        assert!(!grid.contains_rect((0, 0), (11, 1)));
+    }
+}
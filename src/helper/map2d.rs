@@ -22,7 +22,7 @@ impl ParseMapTile for u32 {
     }
 }
 
-type Coords = (usize, usize);
+pub type Coords = (usize, usize);
 
 pub struct Map2D<T> {
     data: Vec<T>,
@@ -51,12 +51,28 @@ impl<T> Map2D<T> {
         }
     }
 
+    /// Creates a map by calling `f` for the coordinates of each tile, row by row.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(Coords) -> T) -> Self {
+        let data = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(&mut f)
+            .collect();
+        Self::new(data, width, height)
+    }
+
     pub fn get(&self, (x, y): Coords) -> Option<&T> {
         if x >= self.width {
             return None;
         }
         self.data.get(x + self.width * y)
     }
+
+    pub fn get_mut(&mut self, (x, y): Coords) -> Option<&mut T> {
+        if x >= self.width {
+            return None;
+        }
+        self.data.get_mut(x + self.width * y)
+    }
     // We accept a closure `F` that takes a char and returns a T
     fn from_input_with_transform<F>(input: &str, transform: F) -> Self
     where
@@ -70,7 +86,7 @@ impl<T> Map2D<T> {
             .collect();
 
         // Avoid division by zero if input is empty
-        let height = if width > 0 { data.len() / width } else { 0 };
+        let height = data.len().checked_div(width).unwrap_or(0);
 
         Self {
             data,
@@ -160,6 +176,13 @@ mod tests {
         Map2D::<u32>::from_input(input);
     }
 
+    #[test]
+    fn test_map2d_from_fn() {
+        let map2d = Map2D::from_fn(3, 2, |(x, y)| x + 10 * y);
+        assert_eq!(*map2d.data(), vec![0, 1, 2, 10, 11, 12]);
+        assert_eq!(map2d.get((2, 1)), Some(&12));
+    }
+
     #[test]
     fn test_map2d_empty_input() {
         let input = "";
@@ -247,4 +270,39 @@ mod tests {
         assert_eq!(*map2d.get((1, 1)).unwrap(), Tiles::Visited);
         assert_eq!(*map2d.get((2, 1)).unwrap(), Tiles::Empty);
     }
+
+    #[test]
+    fn test_map2d_from_generated_grids() {
+        use crate::helper::property::{check, generate};
+
+        check(generate::grid(1..=8, 0..=8, &['.', '#', 'S']), |input| {
+            let map2d = Map2D::<char>::from_input(input);
+            let rows: Vec<&str> = input.lines().collect();
+            assert_eq!(map2d.height(), rows.len());
+            assert_eq!(map2d.data().len(), map2d.width() * map2d.height());
+            for (y, row) in rows.iter().enumerate() {
+                for (x, c) in row.chars().enumerate() {
+                    assert_eq!(map2d.get((x, y)), Some(&c));
+                }
+                assert_eq!(map2d.get((row.len(), y)), None);
+            }
+
+            let trimmed = Map2D::<char>::from_input(input.trim_end());
+            assert_eq!(trimmed.data(), map2d.data());
+            assert_eq!(trimmed.width(), map2d.width());
+        });
+    }
+
+    #[test]
+    fn test_map2d_never_panics_on_malformed_grids() {
+        use crate::helper::property::{check, generate};
+
+        check(
+            generate::mutated(generate::grid(1..=6, 1..=6, &['.', '#']), 4),
+            |input| {
+                let map2d = Map2D::<char>::from_input(input);
+                map2d.get((map2d.width(), map2d.height()));
+            },
+        );
+    }
 }
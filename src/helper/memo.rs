@@ -0,0 +1,161 @@
+use std::{collections::HashMap, hash::Hash, marker::PhantomData};
+
+use crate::helper::map2d::{Coords, Map2D};
+
+/// Storage backend of a [`Memo`].
+pub trait MemoStore<K, V> {
+    fn get(&self, key: &K) -> Option<&V>;
+    fn insert(&mut self, key: K, value: V);
+}
+
+impl<K: Eq + Hash, V> MemoStore<K, V> for HashMap<K, V> {
+    fn get(&self, key: &K) -> Option<&V> {
+        HashMap::get(self, key)
+    }
+
+    fn insert(&mut self, key: K, value: V) {
+        HashMap::insert(self, key, value);
+    }
+}
+
+/// Dense backend for grid coordinates. Panics on keys outside of the grid.
+impl<V> MemoStore<Coords, V> for Map2D<Option<V>> {
+    fn get(&self, key: &Coords) -> Option<&V> {
+        Map2D::get(self, *key)?.as_ref()
+    }
+
+    fn insert(&mut self, key: Coords, value: V) {
+        let slot = self
+            .get_mut(key)
+            .unwrap_or_else(|| panic!("Memo key {key:?} is outside of the grid"));
+        *slot = Some(value);
+    }
+}
+
+/// Caches the results of a recursive computation.
+///
+/// ```
+/// # use advent_of_code::helper::memo::Memo;
+/// fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+///     if n < 2 {
+///         return n;
+///     }
+///     memo.get_or_compute(n, |memo| fib(memo, n - 1) + fib(memo, n - 2))
+/// }
+///
+/// assert_eq!(fib(&mut Memo::new(), 90), 2880067194370816120);
+/// ```
+pub struct Memo<K, V, S = HashMap<K, V>> {
+    store: S,
+    _marker: PhantomData<(K, V)>,
+}
+
+/// A [`Memo`] for grid coordinates, backed by a dense [`Map2D`].
+pub type GridMemo<V> = Memo<Coords, V, Map2D<Option<V>>>;
+
+impl<K: Eq + Hash, V> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::with_store(HashMap::new())
+    }
+}
+
+impl<K: Eq + Hash, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V> GridMemo<V> {
+    pub fn dense(width: usize, height: usize) -> Self {
+        let data = (0..width * height).map(|_| None).collect();
+        Self::with_store(Map2D::new(data, width, height))
+    }
+}
+
+impl<K, V, S: MemoStore<K, V>> Memo<K, V, S> {
+    pub fn with_store(store: S) -> Self {
+        Self {
+            store,
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.store.get(key)
+    }
+
+    /// Returns the cached value for `key`, or calls `compute` and caches its result.
+    /// `compute` receives the memo itself, so it can recurse into `get_or_compute` for other keys.
+    pub fn get_or_compute(&mut self, key: K, compute: impl FnOnce(&mut Self) -> V) -> V
+    where
+        K: Clone,
+        V: Clone,
+    {
+        if let Some(value) = self.store.get(&key) {
+            return value.clone();
+        }
+        let value = compute(self);
+        self.store.insert(key, value.clone());
+        value
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        if n < 2 {
+            return n;
+        }
+        memo.get_or_compute(n, |memo| fib(memo, n - 1) + fib(memo, n - 2))
+    }
+
+    #[test]
+    fn test_memo_recursion() {
+        let mut memo = Memo::new();
+        assert_eq!(fib(&mut memo, 90), 2880067194370816120);
+        assert_eq!(memo.get(&50), Some(&12586269025));
+        assert_eq!(memo.get(&1), None);
+    }
+
+    #[test]
+    fn test_memo_computes_once() {
+        let mut memo: Memo<&str, usize> = Memo::default();
+        let mut calls = 0;
+        for _ in 0..3 {
+            let value = memo.get_or_compute("key", |_| {
+                calls += 1;
+                42
+            });
+            assert_eq!(value, 42);
+        }
+        assert_eq!(calls, 1);
+    }
+
+    /// Number of monotonic lattice paths from `(x, y)` to the bottom-right corner.
+    fn lattice_paths(memo: &mut GridMemo<u64>, (x, y): Coords, size: usize) -> u64 {
+        if x == size - 1 || y == size - 1 {
+            return 1;
+        }
+        memo.get_or_compute((x, y), |memo| {
+            lattice_paths(memo, (x + 1, y), size) + lattice_paths(memo, (x, y + 1), size)
+        })
+    }
+
+    #[test]
+    fn test_grid_memo() {
+        let mut memo = GridMemo::dense(21, 21);
+        assert_eq!(lattice_paths(&mut memo, (0, 0), 21), 137846528820);
+        assert_eq!(memo.get(&(19, 19)), Some(&2));
+        assert_eq!(memo.get(&(20, 20)), None);
+        assert_eq!(memo.get(&(21, 0)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside of the grid")]
+    fn test_grid_memo_out_of_bounds() {
+        let mut memo = GridMemo::dense(2, 2);
+        memo.get_or_compute((0, 2), |_| 1);
+    }
+}
@@ -1 +1,8 @@
+pub mod compress;
+pub mod dag;
+pub mod disjoint_set;
+pub mod geometry;
 pub mod map2d;
+pub mod memo;
+pub mod property;
+pub mod spatial;
@@ -0,0 +1,318 @@
+//! A small property-based testing harness.
+//!
+//! Properties are checked against inputs drawn from a seeded [`Rng`], so that every failure can be
+//! reproduced. The number of cases and the seed can be overridden with the `AOC_PROPERTY_CASES`
+//! and `AOC_PROPERTY_SEED` environment variables.
+//!
+//! ```
+//! # use advent_of_code::helper::property::{check, generate};
+//! check(generate::id_ranges(1..=5, 1000), |input| {
+//!     assert!(!input.contains(' '));
+//! });
+//! ```
+use std::{
+    any::Any,
+    env,
+    fmt::Debug,
+    ops::RangeInclusive,
+    panic::{self, AssertUnwindSafe},
+};
+
+const DEFAULT_CASES: usize = 256;
+const DEFAULT_SEED: u64 = 0x2545_f491_4f6c_dd1d;
+
+/// A deterministic xorshift random number generator.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // xorshift gets stuck on a zero state.
+        Self { state: seed.max(1) }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state << 13;
+        self.state ^= self.state >> 7;
+        self.state ^= self.state << 17;
+        self.state
+    }
+
+    /// A uniformly distributed value in `range`.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (lo, hi) = range.into_inner();
+        assert!(lo <= hi, "empty range {lo}..={hi}");
+        match (hi - lo).checked_add(1) {
+            Some(span) => lo + self.next_u64() % span,
+            None => self.next_u64(),
+        }
+    }
+
+    /// A uniformly distributed index below `len`.
+    pub fn index(&mut self, len: usize) -> usize {
+        assert!(len > 0, "can not pick an index of an empty collection");
+        self.range(0..=len as u64 - 1) as usize
+    }
+
+    /// Returns `true` with a probability of `numerator / denominator`.
+    pub fn ratio(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.range(1..=denominator) <= numerator
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.index(items.len())]
+    }
+}
+
+/// Produces random values of a given input shape.
+pub trait Generator<T> {
+    fn generate(&self, rng: &mut Rng) -> T;
+}
+
+impl<T, F: Fn(&mut Rng) -> T> Generator<T> for F {
+    fn generate(&self, rng: &mut Rng) -> T {
+        self(rng)
+    }
+}
+
+/// How many cases to run and where to start.
+#[derive(Debug, Clone, Copy)]
+pub struct Config {
+    pub cases: usize,
+    pub seed: u64,
+}
+
+impl Default for Config {
+    /// The defaults, overridden by `AOC_PROPERTY_CASES` and `AOC_PROPERTY_SEED` if set.
+    fn default() -> Self {
+        let from_env = |name: &str| env::var(name).ok().and_then(|value| value.parse().ok());
+        Self {
+            cases: from_env("AOC_PROPERTY_CASES").map_or(DEFAULT_CASES, |cases| cases as usize),
+            seed: from_env("AOC_PROPERTY_SEED").unwrap_or(DEFAULT_SEED),
+        }
+    }
+}
+
+/// Checks `property` against inputs drawn from `generator`, with the default [`Config`].
+///
+/// # Panics
+/// Panics with the failing input and the seed to reproduce it if `property` panics for any input.
+pub fn check<T: Debug>(generator: impl Generator<T>, property: impl Fn(&T)) {
+    check_with(Config::default(), generator, property);
+}
+
+/// Checks `property` against `config.cases` inputs drawn from `generator`.
+/// Each case is generated from its own seed, so a single failing case can be rerun on its own.
+pub fn check_with<T: Debug>(config: Config, generator: impl Generator<T>, property: impl Fn(&T)) {
+    let mut seeds = Rng::new(config.seed);
+
+    for case in 0..config.cases {
+        // the first case uses the seed itself, so that a reported seed reruns the failing case.
+        let seed = if case == 0 {
+            config.seed
+        } else {
+            seeds.next_u64()
+        };
+        let input = generator.generate(&mut Rng::new(seed));
+
+        if let Err(err) = panic::catch_unwind(AssertUnwindSafe(|| property(&input))) {
+            let message = panic_message(err.as_ref());
+            panic!(
+                "property failed at case {case} (rerun with AOC_PROPERTY_SEED={seed} AOC_PROPERTY_CASES=1): {message}\ninput: {input:?}"
+            );
+        }
+    }
+}
+
+/// The message of a caught panic.
+fn panic_message(err: &(dyn Any + Send)) -> &str {
+    err.downcast_ref::<String>()
+        .map(String::as_str)
+        .or_else(|| err.downcast_ref::<&str>().copied())
+        .unwrap_or("<non-string panic>")
+}
+
+/// Asserts that two implementations agree on every generated input.
+pub fn check_equivalent<T: Debug, R: Debug + PartialEq>(
+    generator: impl Generator<T>,
+    expected: impl Fn(&T) -> R,
+    actual: impl Fn(&T) -> R,
+) {
+    check(generator, |input| {
+        assert_eq!(expected(input), actual(input))
+    });
+}
+
+/// Generators for the input shapes that come up in puzzles.
+pub mod generate {
+    use std::ops::RangeInclusive;
+
+    use super::{Generator, Rng};
+
+    pub fn integers(range: RangeInclusive<u64>) -> impl Generator<u64> {
+        move |rng: &mut Rng| rng.range(range.clone())
+    }
+
+    /// A vector with a length in `len`.
+    pub fn vec_of<T>(
+        len: RangeInclusive<usize>,
+        item: impl Generator<T>,
+    ) -> impl Generator<Vec<T>> {
+        move |rng: &mut Rng| {
+            let len = rng.range(*len.start() as u64..=*len.end() as u64) as usize;
+            (0..len).map(|_| item.generate(rng)).collect()
+        }
+    }
+
+    /// A number that consists of a digit pattern repeated `repetitions` times, e.g. `123123`.
+    pub fn repeated_digits(
+        pattern_len: RangeInclusive<u32>,
+        repetitions: RangeInclusive<u32>,
+    ) -> impl Generator<u64> {
+        move |rng: &mut Rng| {
+            let len = rng.range(u64::from(*pattern_len.start())..=u64::from(*pattern_len.end()));
+            let pattern = rng.range(10u64.pow(len as u32 - 1)..=10u64.pow(len as u32) - 1);
+            let repetitions =
+                rng.range(u64::from(*repetitions.start())..=u64::from(*repetitions.end()));
+            (0..repetitions).fold(0, |acc, _| acc * 10u64.pow(len as u32) + pattern)
+        }
+    }
+
+    /// Inclusive ranges `(start, end)` with `start <= end <= max`.
+    pub fn ranges(len: RangeInclusive<usize>, max: u64) -> impl Generator<Vec<(u64, u64)>> {
+        vec_of(len, move |rng: &mut Rng| {
+            let start = rng.range(0..=max);
+            (start, rng.range(start..=max))
+        })
+    }
+
+    /// Comma separated ranges on a single line, e.g. `11-22,95-115`.
+    pub fn id_ranges(len: RangeInclusive<usize>, max: u64) -> impl Generator<String> {
+        let ranges = ranges(len, max);
+        move |rng: &mut Rng| {
+            ranges
+                .generate(rng)
+                .iter()
+                .map(|(start, end)| format!("{start}-{end}"))
+                .collect::<Vec<_>>()
+                .join(",")
+        }
+    }
+
+    /// A rectangular grid of characters drawn from `alphabet`, one row per line.
+    pub fn grid(
+        width: RangeInclusive<usize>,
+        height: RangeInclusive<usize>,
+        alphabet: &'static [char],
+    ) -> impl Generator<String> {
+        move |rng: &mut Rng| {
+            let w = rng.range(*width.start() as u64..=*width.end() as u64) as usize;
+            let h = rng.range(*height.start() as u64..=*height.end() as u64) as usize;
+            (0..h)
+                .map(|_| (0..w).map(|_| *rng.choose(alphabet)).collect::<String>() + "\n")
+                .collect()
+        }
+    }
+
+    /// Corrupts a well-formed input by deleting, duplicating or replacing a few characters,
+    /// to check that parsers fail gracefully.
+    pub fn mutated(input: impl Generator<String>, max_mutations: usize) -> impl Generator<String> {
+        const NOISE: &[char] = &['-', ',', '\n', ' ', '#', '(', ')', '{', '}', '0', '9', 'x'];
+
+        move |rng: &mut Rng| {
+            let mut chars: Vec<char> = input.generate(rng).chars().collect();
+            for _ in 0..rng.range(1..=max_mutations as u64) {
+                if chars.is_empty() {
+                    chars.push(*rng.choose(NOISE));
+                    continue;
+                }
+                let i = rng.index(chars.len());
+                match rng.range(0..=2) {
+                    0 => {
+                        chars.remove(i);
+                    }
+                    1 => chars.insert(i, chars[i]),
+                    _ => chars[i] = *rng.choose(NOISE),
+                }
+            }
+            chars.into_iter().collect()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rng_range_bounds() {
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            assert!((3..=5).contains(&rng.range(3..=5)));
+        }
+        assert_eq!(rng.range(4..=4), 4);
+        rng.range(0..=u64::MAX);
+    }
+
+    #[test]
+    fn test_generators_produce_their_shape() {
+        check(generate::id_ranges(1..=4, 100), |input| {
+            for range in input.split(',') {
+                let (start, end) = range.split_once('-').unwrap();
+                assert!(start.parse::<u64>().unwrap() <= end.parse::<u64>().unwrap());
+            }
+        });
+        check(generate::grid(1..=5, 1..=5, &['.', '#']), |input| {
+            let width = input.lines().next().unwrap().len();
+            assert!(input.lines().all(|line| line.len() == width));
+        });
+        check(generate::repeated_digits(1..=3, 2..=3), |n| {
+            let s = n.to_string();
+            assert!(s.len() >= 2);
+        });
+    }
+
+    /// The report of a failing property, and the input it failed on.
+    fn failure(config: Config) -> (String, String) {
+        let err = panic::catch_unwind(|| {
+            check_with(config, generate::integers(0..=100), |n| assert!(*n < 90))
+        })
+        .unwrap_err();
+        let report = panic_message(err.as_ref()).to_string();
+        let (_, input) = report.split_once("\ninput: ").unwrap();
+        (report.clone(), input.to_string())
+    }
+
+    #[test]
+    fn test_check_reports_seed() {
+        let (report, input) = failure(Config {
+            cases: DEFAULT_CASES,
+            seed: DEFAULT_SEED,
+        });
+        let (_, rest) = report.split_once("rerun with AOC_PROPERTY_SEED=").unwrap();
+        let (seed, rest) = rest.split_once(' ').unwrap();
+        assert!(rest.starts_with("AOC_PROPERTY_CASES=1)"));
+
+        let (rerun, rerun_input) = failure(Config {
+            cases: 1,
+            seed: seed.parse().unwrap(),
+        });
+        assert!(rerun.starts_with("property failed at case 0 "));
+        assert_eq!(rerun_input, input);
+    }
+
+    #[test]
+    fn test_check_is_reproducible() {
+        let config = Config { cases: 10, seed: 3 };
+        let collect = || {
+            let seen = std::cell::RefCell::new(vec![]);
+            check_with(config, generate::integers(0..=1000), |n| {
+                seen.borrow_mut().push(*n)
+            });
+            seen.into_inner()
+        };
+        assert_eq!(collect(), collect());
+    }
+}
@@ -0,0 +1,376 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::BinaryHeap,
+};
+
+use crate::helper::disjoint_set::DisjointSet;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Point3 {
+    pub x: i64,
+    pub y: i64,
+    pub z: i64,
+}
+
+impl Point3 {
+    pub fn new(x: i64, y: i64, z: i64) -> Self {
+        Self { x, y, z }
+    }
+
+    fn coord(&self, axis: usize) -> i64 {
+        match axis {
+            0 => self.x,
+            1 => self.y,
+            _ => self.z,
+        }
+    }
+
+    pub fn distance_squared(&self, other: &Point3) -> u128 {
+        let dx = self.x.abs_diff(other.x) as u128;
+        let dy = self.y.abs_diff(other.y) as u128;
+        let dz = self.z.abs_diff(other.z) as u128;
+        dx * dx + dy * dy + dz * dz
+    }
+}
+
+/// Axis aligned bounding box of a subtree, as `(min, max)` per axis.
+type Bounds = [(i64, i64); 3];
+
+fn distance_squared_to_bounds(p: &Point3, bounds: &Bounds) -> u128 {
+    (0..3)
+        .map(|axis| {
+            let (min, max) = bounds[axis];
+            let c = p.coord(axis);
+            let d = if c < min {
+                min.abs_diff(c)
+            } else if c > max {
+                c.abs_diff(max)
+            } else {
+                0
+            } as u128;
+            d * d
+        })
+        .sum()
+}
+
+/// A static k-d tree over a set of points, stored implicitly in a single array:
+/// the subtree covering `order[lo..hi]` has its splitting point at `order[(lo + hi) / 2]`.
+#[derive(Debug, Clone)]
+pub struct KdTree {
+    points: Vec<Point3>,
+    order: Vec<usize>,
+}
+
+impl KdTree {
+    pub fn new(points: &[Point3]) -> Self {
+        fn build(points: &[Point3], order: &mut [usize], depth: usize) {
+            if order.len() <= 1 {
+                return;
+            }
+            let axis = depth % 3;
+            let mid = order.len() / 2;
+            order.select_nth_unstable_by_key(mid, |&i| points[i].coord(axis));
+            let (left, right) = order.split_at_mut(mid);
+            build(points, left, depth + 1);
+            build(points, &mut right[1..], depth + 1);
+        }
+
+        let mut order: Vec<usize> = (0..points.len()).collect();
+        build(points, &mut order, 0);
+        Self {
+            points: points.to_vec(),
+            order,
+        }
+    }
+
+    pub fn points(&self) -> &[Point3] {
+        &self.points
+    }
+
+    /// Iterates over all points by increasing distance to `query`, as `(distance², index)`.
+    /// Points at the same distance are yielded by increasing index.
+    pub fn nearest(&self, query: Point3) -> impl Iterator<Item = (u128, usize)> + '_ {
+        let mut search = NearestSearch::new(self, query);
+        std::iter::from_fn(move || search.next(self))
+    }
+}
+
+/// Pending work of an incremental nearest neighbor search. Ordered by distance, then by
+/// preferring subtrees over points, so that equidistant points are all known before they are yielded.
+#[derive(Debug, Clone)]
+enum Candidate {
+    Subtree {
+        lo: usize,
+        hi: usize,
+        depth: usize,
+        bounds: Bounds,
+    },
+    Point {
+        index: usize,
+    },
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    distance: u128,
+    candidate: Candidate,
+}
+
+impl Entry {
+    fn key(&self) -> (u128, u8, usize) {
+        match self.candidate {
+            Candidate::Subtree { lo, .. } => (self.distance, 0, lo),
+            Candidate::Point { index } => (self.distance, 1, index),
+        }
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+
+impl Eq for Entry {}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(&other.key())
+    }
+}
+
+/// Best-first traversal of a [`KdTree`]. Does not borrow the tree so that many searches can be kept around.
+#[derive(Debug, Clone)]
+struct NearestSearch {
+    query: Point3,
+    queue: BinaryHeap<Reverse<Entry>>,
+}
+
+impl NearestSearch {
+    fn new(tree: &KdTree, query: Point3) -> Self {
+        let mut queue = BinaryHeap::new();
+        if !tree.order.is_empty() {
+            queue.push(Reverse(Entry {
+                distance: 0,
+                candidate: Candidate::Subtree {
+                    lo: 0,
+                    hi: tree.order.len(),
+                    depth: 0,
+                    bounds: [(i64::MIN, i64::MAX); 3],
+                },
+            }));
+        }
+        Self { query, queue }
+    }
+
+    fn next(&mut self, tree: &KdTree) -> Option<(u128, usize)> {
+        while let Some(Reverse(entry)) = self.queue.pop() {
+            let (lo, hi, depth, bounds) = match entry.candidate {
+                Candidate::Point { index } => return Some((entry.distance, index)),
+                Candidate::Subtree {
+                    lo,
+                    hi,
+                    depth,
+                    bounds,
+                } => (lo, hi, depth, bounds),
+            };
+
+            let mid = (lo + hi) / 2;
+            let index = tree.order[mid];
+            let point = tree.points[index];
+            self.queue.push(Reverse(Entry {
+                distance: self.query.distance_squared(&point),
+                candidate: Candidate::Point { index },
+            }));
+
+            let axis = depth % 3;
+            let split = point.coord(axis);
+            let mut left_bounds = bounds;
+            left_bounds[axis].1 = split;
+            let mut right_bounds = bounds;
+            right_bounds[axis].0 = split;
+
+            for (lo, hi, bounds) in [(lo, mid, left_bounds), (mid + 1, hi, right_bounds)] {
+                if lo < hi {
+                    self.queue.push(Reverse(Entry {
+                        distance: distance_squared_to_bounds(&self.query, &bounds),
+                        candidate: Candidate::Subtree {
+                            lo,
+                            hi,
+                            depth: depth + 1,
+                            bounds,
+                        },
+                    }));
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Lazily yields all pairs of points as `(distance², i, j)` with `i < j`,
+/// ordered by distance, then by `i`, then by `j`.
+pub struct ClosestPairs {
+    tree: KdTree,
+    searches: Vec<NearestSearch>,
+    pending: BinaryHeap<Reverse<(u128, usize, usize)>>,
+}
+
+impl ClosestPairs {
+    /// Advances the search of point `i` to its next neighbor `j > i`.
+    fn schedule(&mut self, i: usize) {
+        while let Some((distance, j)) = self.searches[i].next(&self.tree) {
+            if j > i {
+                self.pending.push(Reverse((distance, i, j)));
+                return;
+            }
+        }
+    }
+}
+
+impl Iterator for ClosestPairs {
+    type Item = (u128, usize, usize);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse(pair) = self.pending.pop()?;
+        self.schedule(pair.1);
+        Some(pair)
+    }
+}
+
+/// See [`ClosestPairs`].
+pub fn closest_pairs(points: &[Point3]) -> ClosestPairs {
+    let tree = KdTree::new(points);
+    let searches = points
+        .iter()
+        .map(|&p| NearestSearch::new(&tree, p))
+        .collect();
+    let mut pairs = ClosestPairs {
+        tree,
+        searches,
+        pending: BinaryHeap::new(),
+    };
+    for i in 0..points.len() {
+        pairs.schedule(i);
+    }
+    pairs
+}
+
+/// Builds a minimum spanning tree with Kruskal's algorithm on top of [`closest_pairs`].
+/// Returns the edges as `(distance², i, j)` in the order they were added.
+pub fn minimum_spanning_tree(points: &[Point3]) -> Vec<(u128, usize, usize)> {
+    let mut sets = DisjointSet::new(points.len());
+    let mut edges = Vec::with_capacity(points.len().saturating_sub(1));
+
+    for (distance, i, j) in closest_pairs(points) {
+        if edges.len() + 1 >= points.len() {
+            break;
+        }
+        if sets.union(i, j) {
+            edges.push((distance, i, j));
+        }
+    }
+    edges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Deterministic pseudo-random points in a small cube, so that there are plenty of ties.
+    fn random_points(n: usize, range: i64) -> Vec<Point3> {
+        let mut state = 0x2545_f491_4f6c_dd1d_u64;
+        let mut next = || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state % range as u64) as i64 - range / 2
+        };
+        (0..n)
+            .map(|_| Point3::new(next(), next(), next()))
+            .collect()
+    }
+
+    fn brute_force_pairs(points: &[Point3]) -> Vec<(u128, usize, usize)> {
+        let mut pairs = Vec::new();
+        for i in 0..points.len() {
+            for j in (i + 1)..points.len() {
+                pairs.push((points[i].distance_squared(&points[j]), i, j));
+            }
+        }
+        pairs.sort_unstable();
+        pairs
+    }
+
+    #[test]
+    fn test_distance_squared() {
+        let a = Point3::new(162, 817, 812);
+        let b = Point3::new(425, 690, 689);
+        assert_eq!(a.distance_squared(&b), 263 * 263 + 127 * 127 + 123 * 123);
+        assert_eq!(b.distance_squared(&a), a.distance_squared(&b));
+    }
+
+    #[test]
+    fn test_nearest_matches_brute_force() {
+        let points = random_points(200, 50);
+        let tree = KdTree::new(&points);
+        let query = Point3::new(3, -7, 11);
+
+        let mut expected: Vec<(u128, usize)> = points
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (query.distance_squared(p), i))
+            .collect();
+        expected.sort_unstable();
+
+        assert_eq!(tree.nearest(query).collect::<Vec<_>>(), expected);
+    }
+
+    #[test]
+    fn test_closest_pairs_matches_brute_force() {
+        let points = random_points(150, 20);
+        assert_eq!(
+            closest_pairs(&points).collect::<Vec<_>>(),
+            brute_force_pairs(&points)
+        );
+    }
+
+    #[test]
+    fn test_closest_pairs_is_lazy() {
+        let points = random_points(1000, 100_000);
+        let first: Vec<_> = closest_pairs(&points).take(10).collect();
+        assert_eq!(first, brute_force_pairs(&points)[..10]);
+    }
+
+    #[test]
+    fn test_closest_pairs_degenerate() {
+        assert_eq!(closest_pairs(&[]).next(), None);
+        assert_eq!(closest_pairs(&[Point3::new(1, 2, 3)]).next(), None);
+        let same = [Point3::new(1, 1, 1); 3];
+        assert_eq!(
+            closest_pairs(&same).collect::<Vec<_>>(),
+            vec![(0, 0, 1), (0, 0, 2), (0, 1, 2)]
+        );
+    }
+
+    #[test]
+    fn test_minimum_spanning_tree() {
+        let points = random_points(300, 1000);
+        let mst = minimum_spanning_tree(&points);
+        assert_eq!(mst.len(), points.len() - 1);
+
+        // Kruskal on the brute force pairs yields the same tree.
+        let mut sets = DisjointSet::new(points.len());
+        let expected: Vec<_> = brute_force_pairs(&points)
+            .into_iter()
+            .filter(|&(_, i, j)| sets.union(i, j))
+            .collect();
+        assert_eq!(mst, expected);
+    }
+}
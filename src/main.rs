@@ -1,40 +1,84 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, compare, completions, download, example, help, history, read, scaffold, solve, stars,
+    test_examples, time,
+};
 use args::{AppArguments, parse};
 
-#[cfg(feature = "today")]
 use advent_of_code::template::Day;
 #[cfg(feature = "today")]
+use advent_of_code::template::templates;
+#[cfg(feature = "today")]
 use std::process;
 
 mod args {
     use advent_of_code::template::Day;
+    use advent_of_code::template::cli::{self, InputSource, Shell, SolutionArgs};
+    use advent_of_code::template::commands::time::{CompareOptions, ExportOptions};
+    use advent_of_code::template::limits::Limits;
+    use advent_of_code::template::report::ReportOptions;
+    use advent_of_code::template::templates;
     use std::process;
 
     pub enum AppArguments {
         Download {
             day: Day,
         },
+        Example {
+            day: Day,
+            overwrite: bool,
+        },
         Read {
             day: Day,
+            no_pager: bool,
         },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            dry_run: bool,
+            template: String,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
-            submit: Option<u8>,
+            watch: bool,
+            solution_args: SolutionArgs,
         },
         All {
             release: bool,
+            jobs: usize,
+            limits: Limits,
+            reports: ReportOptions,
+        },
+        TestExamples {
+            day: Option<Day>,
+            release: bool,
+        },
+        Compare {
+            day: Day,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            dhat: bool,
+            tag: Option<String>,
+            compare: Option<CompareOptions>,
+            export: Option<ExportOptions>,
+            jobs: usize,
+            limits: Limits,
+            reports: ReportOptions,
+        },
+        History {
+            out: Option<String>,
+        },
+        Stars,
+        Completions {
+            shell: Shell,
+        },
+        Help {
+            command: Option<String>,
         },
         #[cfg(feature = "today")]
         Today,
@@ -42,59 +86,201 @@ mod args {
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
+        let help = args.contains(["-h", "--help"]);
+
+        let command = match args.subcommand()? {
+            Some(command) if help => {
+                return Ok(AppArguments::Help {
+                    command: Some(command),
+                });
+            }
+            Some(command) => command,
+            None if help => return Ok(AppArguments::Help { command: None }),
+            None => {
+                eprintln!("No command specified.\n\n{}", cli::help());
+                process::exit(1);
+            }
+        };
+
+        let Some(spec) = cli::find_command(&command) else {
+            eprintln!("Unknown command: {command}\n\n{}", cli::help());
+            process::exit(1);
+        };
+
+        let app_args = parse_command(&command, &mut args)
+            .map_err(|err| format!("{err}\n\n{}", cli::command_help(spec)))?;
+
+        let remaining = args.finish();
+        if !remaining.is_empty() {
+            return Err(format!(
+                "unknown argument(s): {remaining:?}\n\n{}",
+                cli::command_help(spec)
+            )
+            .into());
+        }
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
+        Ok(app_args)
+    }
+
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, pico_args::Error> {
+        Ok(Limits {
+            timeout: args.opt_value_from_fn("--timeout", cli::parse_timeout)?,
+            total_timeout: args.opt_value_from_fn("--total-timeout", cli::parse_timeout)?,
+            memory_bytes: args.opt_value_from_fn("--memory-limit", cli::parse_size)?,
+        })
+    }
+
+    fn parse_reports(args: &mut pico_args::Arguments) -> Result<ReportOptions, pico_args::Error> {
+        Ok(ReportOptions {
+            junit: args.opt_value_from_str("--junit")?,
+            tap: args.opt_value_from_str("--tap")?,
+        })
+    }
+
+    fn parse_command(
+        command: &str,
+        args: &mut pico_args::Arguments,
+    ) -> Result<AppArguments, Box<dyn std::error::Error>> {
+        let app_args = match command {
+            "all" => AppArguments::All {
+                release: args.contains("--release"),
+                jobs: args
+                    .opt_value_from_fn("--jobs", cli::parse_jobs)?
+                    .unwrap_or(1),
+                limits: parse_limits(args)?,
+                reports: parse_reports(args)?,
+            },
+            "test-examples" => AppArguments::TestExamples {
                 release: args.contains("--release"),
+                day: args.opt_free_from_str()?,
             },
-            Some("time") => {
+            "compare" => AppArguments::Compare {
+                day: args.free_from_str()?,
+            },
+            "time" => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let dhat = args.contains("--dhat");
+                let tag = args.opt_value_from_str("--tag")?;
+                let baseline = args.opt_value_from_str("--baseline")?;
+                let threshold = args.opt_value_from_fn("--threshold", cli::parse_percent)?;
+
+                let compare =
+                    (args.contains("--compare") || baseline.is_some()).then(|| CompareOptions {
+                        baseline,
+                        threshold: threshold.unwrap_or(10.0),
+                    });
+
+                let export = match args.opt_value_from_str("--export")? {
+                    Some(format) => Some(ExportOptions {
+                        format,
+                        out: args.value_from_str("--out")?,
+                    }),
+                    None => None,
+                };
 
                 AppArguments::Time {
+                    jobs: args
+                        .opt_value_from_fn("--jobs", cli::parse_jobs)?
+                        .unwrap_or(1),
+                    limits: parse_limits(args)?,
+                    reports: parse_reports(args)?,
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    dhat,
+                    tag,
+                    compare,
+                    export,
                 }
             }
-            Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+            "history" => AppArguments::History {
+                out: args.opt_value_from_str("--out")?,
             },
-            Some("read") => AppArguments::Read {
+            "stars" => AppArguments::Stars,
+            "download" => AppArguments::Download {
                 day: args.free_from_str()?,
             },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
+            "example" => AppArguments::Example {
                 overwrite: args.contains("--overwrite"),
+                day: args.free_from_str()?,
             },
-            Some("solve") => AppArguments::Solve {
+            "read" => AppArguments::Read {
+                no_pager: args.contains("--no-pager"),
                 day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
             },
-            #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
+            "scaffold" => {
+                let download = args.contains("--download");
+                let overwrite = args.contains("--overwrite");
+                let dry_run = args.contains("--dry-run");
+                let template = args
+                    .opt_value_from_str("--template")?
+                    .unwrap_or_else(|| templates::DEFAULT_TEMPLATE.into());
+
+                if dry_run && download {
+                    return Err("--dry-run cannot be combined with --download".into());
+                }
+
+                AppArguments::Scaffold {
+                    day: args.free_from_str()?,
+                    download,
+                    overwrite,
+                    dry_run,
+                    template,
+                }
             }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
+            "solve" => {
+                let release = args.contains("--release");
+                let dhat = args.contains("--dhat");
+                let submit = args.opt_value_from_fn("--submit", cli::parse_part)?;
+                let watch = args.contains("--watch");
+                let day = args.free_from_str()?;
+                let solution_args = SolutionArgs {
+                    submit,
+                    input: InputSource::parse(args)?,
+                    ..SolutionArgs::default()
+                };
+                solution_args.validate()?;
+
+                if submit.is_some() && watch {
+                    return Err("--submit cannot be combined with --watch".into());
+                }
+                if watch && solution_args.input == Some(InputSource::Stdin) {
+                    return Err("stdin cannot be read more than once with --watch".into());
+                }
+
+                AppArguments::Solve {
+                    day,
+                    release,
+                    dhat,
+                    watch,
+                    solution_args,
+                }
             }
+            "completions" => AppArguments::Completions {
+                shell: args.free_from_str()?,
+            },
+            "help" => AppArguments::Help {
+                command: args.opt_free_from_str()?,
+            },
+            #[cfg(feature = "today")]
+            "today" => AppArguments::Today,
+            _ => unreachable!("commands are validated against the command list"),
         };
 
-        let remaining = args.finish();
-        if !remaining.is_empty() {
-            eprintln!("Warning: unknown argument(s): {remaining:?}.");
-        }
-
         Ok(app_args)
     }
 }
 
+/// Fills the example file of a freshly scaffolded day from the downloaded puzzle.
+/// The example stays empty if the puzzle does not contain one.
+fn fill_example(day: Day) {
+    match example::extract(day, false) {
+        Ok((path, _)) => println!("Filled in example file \"{}\"", path.display()),
+        Err(err) => eprintln!("Left the example file empty: {err}"),
+    }
+}
+
 fn main() {
     match parse() {
         Err(err) => {
@@ -102,33 +288,63 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All {
+                release,
+                jobs,
+                limits,
+                reports,
+            } => all::handle(release, jobs, limits, reports),
+            AppArguments::TestExamples { day, release } => test_examples::handle(day, release),
+            AppArguments::Compare { day } => compare::handle(day),
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                dhat,
+                tag,
+                compare,
+                export,
+                jobs,
+                limits,
+                reports,
+            } => time::handle(
+                day, all, store, dhat, tag, compare, export, jobs, limits, reports,
+            ),
+            AppArguments::History { out } => history::handle(out),
+            AppArguments::Stars => stars::handle(),
+            AppArguments::Completions { shell } => completions::handle(shell),
+            AppArguments::Help { command } => help::handle(command.as_deref()),
             AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
+            AppArguments::Example { day, overwrite } => example::handle(day, overwrite),
+            AppArguments::Read { day, no_pager } => read::handle(day, no_pager),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                dry_run,
+                template,
             } => {
-                scaffold::handle(day, overwrite);
+                scaffold::handle(day, overwrite, dry_run, &template);
                 if download {
                     download::handle(day);
+                    fill_example(day);
                 }
             }
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
+                watch,
+                solution_args,
+            } => solve::handle(day, release, dhat, watch, &solution_args),
             #[cfg(feature = "today")]
             AppArguments::Today => {
                 match Day::today() {
                     Some(day) => {
-                        scaffold::handle(day, false);
+                        scaffold::handle(day, false, false, templates::DEFAULT_TEMPLATE);
                         download::handle(day);
-                        read::handle(day)
+                        fill_example(day);
+                        read::handle(day, false)
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,221 @@
+/// Answers accepted by Advent of Code, recorded on submission and used to render the stars table.
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+
+static ANSWERS_FILE_PATH: &str = "./data/answers.json";
+
+/// Version of the JSON document written by [`Answers::store_file`].
+pub const SCHEMA_VERSION: u8 = 1;
+
+/// The accepted answers of a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answer {
+    pub fn part(&self, part: u8) -> Option<&str> {
+        match part {
+            1 => self.part_1.as_deref(),
+            _ => self.part_2.as_deref(),
+        }
+    }
+}
+
+/// The accepted answers of a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        let mut file = fs::File::create(ANSWERS_FILE_PATH)?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    /// Fails if the file can not be read or parsed, so that callers do not overwrite recorded answers.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(ANSWERS_FILE_PATH) {
+            Ok(contents) => Answers::try_from(contents),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(err) => Err(err.to_string()),
+        }
+        .map_err(|err| format!("could not read {ANSWERS_FILE_PATH}: {err}"))
+    }
+
+    /// Records the accepted answer of a part, overwriting a previous answer.
+    pub fn record(&mut self, day: Day, part: u8, answer: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let entry = &mut self.data[index];
+        match part {
+            1 => entry.part_1 = Some(answer.into()),
+            _ => entry.part_2 = Some(answer.into()),
+        }
+    }
+
+    pub fn get(&self, day: Day) -> Option<&Answer> {
+        self.data.iter().find(|a| a.day == day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(SCHEMA_VERSION)),
+        );
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let version = json
+            .get("version")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("expected `json.version` to be a number.")?;
+
+        if version != f64::from(SCHEMA_VERSION) {
+            return Err(format!("unsupported answers version {version}."));
+        }
+
+        let json_data = json
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let part_json =
+            |part: &Option<String>| part.clone().map_or(JsonValue::Null, JsonValue::String);
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part_1".into(), part_json(&value.part_1));
+        map.insert("part_2".into(), part_json(&value.part_2));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part = |key: &str| match json.get(key) {
+            None | Some(JsonValue::Null) => Ok(None),
+            Some(JsonValue::String(answer)) => Ok(Some(answer.clone())),
+            Some(_) => Err(format!("Expected answer.{key} to be a string or null.")),
+        };
+
+        Ok(Answer {
+            day,
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::day;
+
+    use super::Answers;
+
+    #[test]
+    fn records_answers() {
+        let mut answers = Answers::default();
+        answers.record(day!(3), 2, "12");
+        answers.record(day!(1), 1, "5");
+        answers.record(day!(3), 2, "13");
+
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(1));
+        assert_eq!(answers.get(day!(3)).unwrap().part(2), Some("13"));
+        assert_eq!(answers.get(day!(3)).unwrap().part(1), None);
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, "5");
+        answers.record(day!(2), 2, "a\nb");
+
+        let json = tinyjson::JsonValue::from(answers.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(Answers::try_from(json), Ok(answers));
+    }
+
+    #[test]
+    fn rejects_unknown_version() {
+        let json = r#"{"version":2,"data":[]}"#.to_string();
+        assert!(Answers::try_from(json).is_err());
+    }
+}
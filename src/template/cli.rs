@@ -0,0 +1,946 @@
+/// The command-line interface: a description of every command, used for help texts and shell completions,
+/// and the arguments that the dispatcher passes to solution binaries.
+use std::{fmt::Display, path::PathBuf, str::FromStr, sync::OnceLock, time::Duration};
+
+use crate::template::ANSI_BOLD;
+use crate::template::ANSI_RESET;
+use crate::template::templates;
+
+/// An option of a command.
+pub struct OptionSpec {
+    pub name: &'static str,
+    /// Placeholder of the option value. `None` for flags.
+    pub value: Option<&'static str>,
+    /// Values offered by shell completions.
+    pub choices: &'static [&'static str],
+    pub help: &'static str,
+}
+
+/// A command of the dispatcher, e.g. `solve`.
+pub struct CommandSpec {
+    pub name: &'static str,
+    /// How the command is invoked, without the options.
+    pub usage: &'static str,
+    pub about: &'static str,
+    pub options: &'static [OptionSpec],
+}
+
+const fn flag(name: &'static str, help: &'static str) -> OptionSpec {
+    OptionSpec {
+        name,
+        value: None,
+        choices: &[],
+        help,
+    }
+}
+
+const fn value(name: &'static str, value: &'static str, help: &'static str) -> OptionSpec {
+    OptionSpec {
+        name,
+        value: Some(value),
+        choices: &[],
+        help,
+    }
+}
+
+const fn choice(
+    name: &'static str,
+    value: &'static str,
+    choices: &'static [&'static str],
+    help: &'static str,
+) -> OptionSpec {
+    OptionSpec {
+        name,
+        value: Some(value),
+        choices,
+        help,
+    }
+}
+
+const PARTS: &[&str] = &["1", "2"];
+
+static COMMANDS: &[CommandSpec] = &[
+    CommandSpec {
+        name: "scaffold",
+        usage: "cargo scaffold <day>",
+        about: "Create the solution, example and input files of a day.",
+        options: &[
+            flag("--download", "Download the input and puzzle afterwards."),
+            flag("--overwrite", "Overwrite an existing solution."),
+            flag(
+                "--dry-run",
+                "Print the planned changes without writing files.",
+            ),
+            choice(
+                "--template",
+                "<name>",
+                &templates::BUILTIN_NAMES,
+                "Start from a built-in or user template, e.g. grid.",
+            ),
+        ],
+    },
+    CommandSpec {
+        name: "download",
+        usage: "cargo download <day>",
+        about: "Download the input and puzzle of a day via aoc-cli.",
+        options: &[],
+    },
+    CommandSpec {
+        name: "example",
+        usage: "cargo example <day>",
+        about: "Extract the example input and answers from the downloaded puzzle of a day.",
+        options: &[flag(
+            "--overwrite",
+            "Replace an example file that already has an input.",
+        )],
+    },
+    CommandSpec {
+        name: "read",
+        usage: "cargo read <day>",
+        about: "Print the downloaded puzzle of a day.",
+        options: &[flag(
+            "--no-pager",
+            "Print to the terminal instead of $PAGER or less.",
+        )],
+    },
+    CommandSpec {
+        name: "solve",
+        usage: "cargo solve <day> [-]",
+        about: "Run the solution of a day against its input.",
+        options: &[
+            flag("--release", "Run an optimized build."),
+            flag("--dhat", "Profile heap allocations with DHAT."),
+            choice(
+                "--submit",
+                "<part>",
+                PARTS,
+                "Submit the answer of part 1 or 2.",
+            ),
+            flag(
+                "--watch",
+                "Re-run the examples and the solution on every change.",
+            ),
+            value(
+                "--input",
+                "<path>",
+                "Read the input from a file, or stdin for `-`.",
+            ),
+            value(
+                "--example",
+                "[name]",
+                "Use an example as input, the first one without a name.",
+            ),
+        ],
+    },
+    CommandSpec {
+        name: "all",
+        usage: "cargo all",
+        about: "Run the solutions of all days.",
+        options: &[
+            flag("--release", "Run optimized builds."),
+            value("--jobs", "<n>", "Run up to n days concurrently."),
+            value(
+                "--timeout",
+                "<duration>",
+                "Stop a day after the duration, e.g. 30s or 500ms.",
+            ),
+            value(
+                "--total-timeout",
+                "<duration>",
+                "Skip the remaining days after the duration.",
+            ),
+            value(
+                "--memory-limit",
+                "<size>",
+                "Stop a day above the resident memory, e.g. 512M. Linux only.",
+            ),
+            value(
+                "--junit",
+                "<path>",
+                "Write a JUnit XML report of all parts.",
+            ),
+            value("--tap", "<path>", "Write a TAP report of all parts."),
+        ],
+    },
+    CommandSpec {
+        name: "test-examples",
+        usage: "cargo test-examples [day]",
+        about: "Check the solutions against the answers declared in their examples.",
+        options: &[flag("--release", "Run optimized builds.")],
+    },
+    CommandSpec {
+        name: "compare",
+        usage: "cargo compare <day>",
+        about: "Bench the variants of each part of a day against each other.",
+        options: &[],
+    },
+    CommandSpec {
+        name: "time",
+        usage: "cargo time [day]",
+        about: "Benchmark the solutions.",
+        options: &[
+            flag(
+                "--all",
+                "Bench all days, including the ones already stored.",
+            ),
+            flag(
+                "--store",
+                "Store the timings in the readme and the benchmark history.",
+            ),
+            flag("--dhat", "Profile heap allocations in a separate pass."),
+            value("--tag", "<name>", "Name the run in the benchmark history."),
+            flag("--compare", "Compare against the benchmark history."),
+            value(
+                "--baseline",
+                "<tag|commit>",
+                "Run to compare against, implies --compare.",
+            ),
+            value(
+                "--threshold",
+                "<percent>",
+                "Slowdown reported as regression, defaults to 10.",
+            ),
+            choice(
+                "--export",
+                "<format>",
+                &["csv", "json", "md", "svg"],
+                "Export the timings, requires --out.",
+            ),
+            value("--out", "<path>", "Path of the export."),
+            value(
+                "--jobs",
+                "<n>",
+                "Profile up to n days concurrently with --dhat. Benches always run one at a time.",
+            ),
+            value(
+                "--timeout",
+                "<duration>",
+                "Stop a day after the duration, e.g. 30s or 500ms.",
+            ),
+            value(
+                "--total-timeout",
+                "<duration>",
+                "Skip the remaining days after the duration.",
+            ),
+            value(
+                "--memory-limit",
+                "<size>",
+                "Stop a day above the resident memory, e.g. 512M. Linux only.",
+            ),
+            value(
+                "--junit",
+                "<path>",
+                "Write a JUnit XML report of all parts.",
+            ),
+            value("--tap", "<path>", "Write a TAP report of all parts."),
+        ],
+    },
+    CommandSpec {
+        name: "history",
+        usage: "cargo history",
+        about: "Export the benchmark history as CSV.",
+        options: &[value(
+            "--out",
+            "<path>",
+            "Write to a file instead of stdout.",
+        )],
+    },
+    CommandSpec {
+        name: "stars",
+        usage: "cargo stars",
+        about: "Regenerate the stars table of the readme from the recorded answers.",
+        options: &[],
+    },
+    CommandSpec {
+        name: "today",
+        usage: "cargo today",
+        about: "Scaffold, download and read the current day.",
+        options: &[],
+    },
+    CommandSpec {
+        name: "completions",
+        usage: "cargo run -- completions <bash|zsh|fish>",
+        about: "Print a shell completion script for the cargo aliases and the advent_of_code binary.",
+        options: &[],
+    },
+    CommandSpec {
+        name: "help",
+        usage: "cargo run -- help [command]",
+        about: "Print the help of all commands or of a single one.",
+        options: &[],
+    },
+];
+
+/// The commands available in this build.
+pub fn commands() -> impl Iterator<Item = &'static CommandSpec> {
+    COMMANDS
+        .iter()
+        .filter(|command| command.name != "today" || cfg!(feature = "today"))
+}
+
+pub fn find_command(name: &str) -> Option<&'static CommandSpec> {
+    commands().find(|command| command.name == name)
+}
+
+/// Renders the list of all commands.
+pub fn help() -> String {
+    let width = commands().map(|c| c.name.len()).max().unwrap_or(0);
+    let mut lines = vec![format!("{ANSI_BOLD}Commands{ANSI_RESET}"), String::new()];
+    for command in commands() {
+        lines.push(format!("  {:width$}  {}", command.name, command.about));
+    }
+    lines.push(String::new());
+    lines.push("Run `cargo <command> --help` for the options of a command.".into());
+    lines.join("\n")
+}
+
+/// Renders the usage and options of `command`.
+pub fn command_help(command: &CommandSpec) -> String {
+    let option_names: Vec<String> = command
+        .options
+        .iter()
+        .map(|option| match option.value {
+            Some(value) => format!("{} {value}", option.name),
+            None => option.name.into(),
+        })
+        .collect();
+    let width = option_names.iter().map(String::len).max().unwrap_or(0);
+
+    let mut lines = vec![
+        command.about.to_string(),
+        String::new(),
+        format!(
+            "{ANSI_BOLD}Usage:{ANSI_RESET} {}{}",
+            command.usage,
+            if command.options.is_empty() {
+                ""
+            } else {
+                " [options]"
+            }
+        ),
+    ];
+
+    if !command.options.is_empty() {
+        lines.push(String::new());
+        lines.push(format!("{ANSI_BOLD}Options{ANSI_RESET}"));
+        lines.push(String::new());
+        for (option, name) in command.options.iter().zip(&option_names) {
+            lines.push(format!("  {name:width$}  {}", option.help));
+        }
+    }
+
+    lines.join("\n")
+}
+
+/// Validates a part number passed to `--submit`.
+pub fn parse_part(s: &str) -> Result<u8, String> {
+    match s {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        x => Err(format!("expected part 1 or 2, got `{x}`")),
+    }
+}
+
+/// Validates a number of concurrent jobs, e.g. for `--jobs`.
+pub fn parse_jobs(s: &str) -> Result<usize, String> {
+    match s.parse::<usize>() {
+        Ok(jobs) if jobs > 0 => Ok(jobs),
+        _ => Err(format!("expected a positive number of jobs, got `{s}`")),
+    }
+}
+
+/// Parses a duration, e.g. `500ms`, `30s` or `2m`, for `--timeout`. Plain numbers are seconds.
+pub fn parse_timeout(s: &str) -> Result<Duration, String> {
+    let (number, unit) = s.split_at(s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len()));
+    let seconds = match unit {
+        "ms" => 0.001,
+        "" | "s" => 1.0,
+        "m" => 60.0,
+        "h" => 3600.0,
+        _ => {
+            return Err(format!(
+                "expected a duration like 500ms, 30s or 2m, got `{s}`"
+            ));
+        }
+    };
+    match number.parse::<f64>() {
+        Ok(value) if value.is_finite() && value > 0.0 => {
+            Ok(Duration::from_secs_f64(value * seconds))
+        }
+        _ => Err(format!("expected a positive duration, got `{s}`")),
+    }
+}
+
+/// Parses a number of bytes with an optional binary unit, e.g. `512M` or `2GiB`, for `--memory-limit`.
+pub fn parse_size(s: &str) -> Result<u64, String> {
+    let (number, unit) = s.split_at(s.find(|c: char| c.is_ascii_alphabetic()).unwrap_or(s.len()));
+    let exponent = match unit.trim_end_matches("iB").trim_end_matches('B') {
+        "" => 0,
+        "K" | "k" => 1,
+        "M" => 2,
+        "G" => 3,
+        _ => return Err(format!("expected a size like 512M or 2G, got `{s}`")),
+    };
+    match number.parse::<u64>() {
+        Ok(value) if value > 0 => value
+            .checked_mul(1024_u64.pow(exponent))
+            .ok_or(format!("size `{s}` is too large")),
+        _ => Err(format!("expected a positive size, got `{s}`")),
+    }
+}
+
+/// Validates a percentage, e.g. for `--threshold`.
+pub fn parse_percent(s: &str) -> Result<f64, String> {
+    match s.parse::<f64>() {
+        Ok(percent) if percent.is_finite() && percent >= 0.0 => Ok(percent),
+        _ => Err(format!("expected a non-negative percentage, got `{s}`")),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            x => Err(format!(
+                "unsupported shell `{x}`, expected bash, zsh or fish"
+            )),
+        }
+    }
+}
+
+/// Name of the dispatcher binary the completions are registered for.
+const BIN_NAME: &str = "advent_of_code";
+
+/// Whether `command` has a cargo alias, e.g. `cargo solve`. Aliases are completed as cargo subcommands.
+fn has_alias(command: &CommandSpec) -> bool {
+    command
+        .usage
+        .strip_prefix("cargo ")
+        .and_then(|usage| usage.strip_prefix(command.name))
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with(' '))
+}
+
+/// Renders a completion script of the dispatcher binary and its cargo aliases for `shell`.
+pub fn completions(shell: Shell) -> String {
+    match shell {
+        Shell::Bash => bash_completions(),
+        Shell::Zsh => zsh_completions(),
+        Shell::Fish => fish_completions(),
+    }
+}
+
+fn bash_completions() -> String {
+    let names: Vec<&str> = commands().map(|c| c.name).collect();
+
+    let mut script = vec![
+        format!("_{BIN_NAME}() {{"),
+        "    local cur=\"${COMP_WORDS[COMP_CWORD]}\"".into(),
+        "    local prev=\"${COMP_WORDS[COMP_CWORD-1]}\"".into(),
+        // cargo forwards the completion of `cargo solve` to the command `cargo-solve`.
+        "    local command=\"${COMP_WORDS[0]#cargo-}\"".into(),
+        "    if [ \"$command\" = \"${COMP_WORDS[0]}\" ]; then".into(),
+        "        if [ \"$COMP_CWORD\" -eq 1 ]; then".into(),
+        format!(
+            "            COMPREPLY=($(compgen -W \"{}\" -- \"$cur\"))",
+            names.join(" ")
+        ),
+        "            return".into(),
+        "        fi".into(),
+        "        command=\"${COMP_WORDS[1]}\"".into(),
+        "    fi".into(),
+        "    case \"$command:$prev\" in".into(),
+    ];
+
+    for command in commands() {
+        for option in command.options.iter().filter(|o| !o.choices.is_empty()) {
+            script.push(format!(
+                "        {}:{}) COMPREPLY=($(compgen -W \"{}\" -- \"$cur\")); return ;;",
+                command.name,
+                option.name,
+                option.choices.join(" ")
+            ));
+        }
+    }
+    script.push("    esac".into());
+    script.push("    case \"$command\" in".into());
+
+    for command in commands() {
+        let options: Vec<&str> = command.options.iter().map(|o| o.name).collect();
+        let words = match command.name {
+            "help" => names.join(" "),
+            "completions" => "bash zsh fish".into(),
+            _ => options.join(" "),
+        };
+        script.push(format!(
+            "        {}) COMPREPLY=($(compgen -W \"{words}\" -- \"$cur\")) ;;",
+            command.name
+        ));
+    }
+
+    let aliases: Vec<String> = commands()
+        .filter(|command| has_alias(command))
+        .map(|command| format!("cargo-{}", command.name))
+        .collect();
+
+    script.push("    esac".into());
+    script.push("}".into());
+    script.push(format!("complete -F _{BIN_NAME} {BIN_NAME}"));
+    script.push(format!("complete -F _{BIN_NAME} {}", aliases.join(" ")));
+    script.push(String::new());
+    script.join("\n")
+}
+
+/// Escapes a description for a single-quoted zsh string.
+fn zsh_escape(s: &str) -> String {
+    s.replace('\'', "'\\''")
+        .replace('[', "\\[")
+        .replace(']', "\\]")
+        .replace(':', "\\:")
+}
+
+/// The `_arguments` specs of `command`.
+fn zsh_arguments(command: &CommandSpec) -> Vec<String> {
+    match command.name {
+        "help" => vec!["'1:command:->commands'".into()],
+        "completions" => vec!["'1:shell:(bash zsh fish)'".into()],
+        _ => command
+            .options
+            .iter()
+            .map(|option| {
+                let value = match option.value {
+                    // optional values, e.g. `[name]`.
+                    Some(value) if value.starts_with('[') => {
+                        format!("::{}:", value.trim_matches(['[', ']']))
+                    }
+                    Some(value) if option.choices.is_empty() => format!(":{value}:"),
+                    Some(value) => format!(":{value}:({})", option.choices.join(" ")),
+                    None => String::new(),
+                };
+                format!("'{}[{}]{value}'", option.name, zsh_escape(option.help))
+            })
+            .collect(),
+    }
+}
+
+fn zsh_completions() -> String {
+    let mut script = vec![
+        format!("#compdef {BIN_NAME}"),
+        String::new(),
+        format!("_{BIN_NAME}() {{"),
+        "    local -a commands".into(),
+        "    commands=(".into(),
+    ];
+
+    for command in commands() {
+        script.push(format!(
+            "        '{}:{}'",
+            command.name,
+            zsh_escape(command.about)
+        ));
+    }
+
+    script.extend([
+        "    )".into(),
+        "    if (( CURRENT == 2 )); then".into(),
+        "        _describe 'command' commands".into(),
+        "        return".into(),
+        "    fi".into(),
+        "    case $words[2] in".into(),
+    ]);
+
+    for command in commands() {
+        let arguments = zsh_arguments(command);
+        if arguments.is_empty() {
+            continue;
+        }
+        script.push(format!(
+            "        {}) _arguments {} ;;",
+            command.name,
+            arguments.join(" ")
+        ));
+    }
+
+    script.extend([
+        "    esac".into(),
+        "    if [[ $state == commands ]]; then".into(),
+        "        _describe 'command' commands".into(),
+        "    fi".into(),
+        "}".into(),
+        String::new(),
+    ]);
+
+    // cargo completes `cargo solve` with the function `_cargo-solve`, if it is defined.
+    for command in commands().filter(|command| has_alias(command)) {
+        let arguments = zsh_arguments(command);
+        if !arguments.is_empty() {
+            script.push(format!(
+                "_cargo-{}() {{ _arguments {} }}",
+                command.name,
+                arguments.join(" ")
+            ));
+        }
+    }
+
+    script.extend([
+        String::new(),
+        format!("if [[ $funcstack[1] == _{BIN_NAME} ]]; then"),
+        format!("    _{BIN_NAME} \"$@\""),
+        "else".into(),
+        format!("    compdef _{BIN_NAME} {BIN_NAME}"),
+        "fi".into(),
+        String::new(),
+    ]);
+    script.join("\n")
+}
+
+/// Renders the completions of the options of `command` when run as `program <command>`.
+fn fish_options(program: &str, command: &CommandSpec) -> Vec<String> {
+    command
+        .options
+        .iter()
+        .map(|option| {
+            let mut line = format!(
+                "complete -c {program} -f -n '__fish_seen_subcommand_from {}' -l {} -d '{}'",
+                command.name,
+                option.name.trim_start_matches("--"),
+                option.help.replace('\'', "\\'")
+            );
+            if option.value.is_some_and(|value| !value.starts_with('[')) {
+                line.push_str(" -r");
+            }
+            if !option.choices.is_empty() {
+                line.push_str(&format!(" -a '{}'", option.choices.join(" ")));
+            }
+            line
+        })
+        .collect()
+}
+
+fn fish_completions() -> String {
+    let mut script = vec![];
+
+    for command in commands() {
+        script.push(format!(
+            "complete -c {BIN_NAME} -f -n '__fish_use_subcommand' -a {} -d '{}'",
+            command.name,
+            command.about.replace('\'', "\\'")
+        ));
+    }
+
+    for command in commands() {
+        script.extend(fish_options(BIN_NAME, command));
+    }
+
+    script.push(format!(
+        "complete -c {BIN_NAME} -f -n '__fish_seen_subcommand_from completions' -a 'bash zsh fish'"
+    ));
+
+    // fish merges these with its own completions of cargo, which already list the aliases.
+    for command in commands().filter(|command| has_alias(command)) {
+        script.extend(fish_options("cargo", command));
+    }
+
+    script.push(String::new());
+    script.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Arguments of a solution binary, e.g. `cargo run --bin 01 -- --submit 1`.
+/// The dispatcher builds them with [`SolutionArgs::to_args`], the binaries parse them with [`SolutionArgs::parse`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SolutionArgs {
+    /// Part whose answer is submitted.
+    pub submit: Option<u8>,
+    /// Bench each part instead of running it once.
+    pub time: bool,
+    /// Print machine readable statistics of each part, for `cargo time`.
+    pub stats: bool,
+    /// Print the machine readable answer of each part, for the summary of `cargo all` and `cargo time`.
+    pub results: bool,
+    /// Print the results of the examples, for `cargo test-examples`.
+    pub examples: bool,
+    /// Bench all variants of each part, for `cargo compare`.
+    pub variants: bool,
+    /// Where to read the input from instead of `data/inputs/NN.txt`.
+    pub input: Option<InputSource>,
+}
+
+/// Where a solution reads its input from, if not from `data/inputs/NN.txt`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum InputSource {
+    /// `--input <path>`.
+    File(PathBuf),
+    /// `--example [name]`. Without a name, the first example of the day.
+    Example(Option<String>),
+    /// `-` or `--input -`.
+    Stdin,
+}
+
+impl InputSource {
+    /// Parses `--input <path>`, `--example [name]` or `-`.
+    /// Has to be called after all other arguments were taken, as the example name is a free argument.
+    pub fn parse(args: &mut pico_args::Arguments) -> Result<Option<Self>, String> {
+        let file: Option<String> = args
+            .opt_value_from_str("--input")
+            .map_err(|err| err.to_string())?;
+        let example = args.contains("--example");
+        let free: Option<String> = args.opt_free_from_str().map_err(|err| err.to_string())?;
+
+        match (file, example, free) {
+            (None, false, None) => Ok(None),
+            (Some(path), false, None) if path == "-" => Ok(Some(InputSource::Stdin)),
+            (Some(path), false, None) => Ok(Some(InputSource::File(path.into()))),
+            (None, true, name) => Ok(Some(InputSource::Example(name))),
+            (None, false, Some(free)) if free == "-" => Ok(Some(InputSource::Stdin)),
+            (None, false, Some(free)) => Err(format!("unknown argument `{free}`")),
+            _ => Err("only one of --input, --example and - can be used".into()),
+        }
+    }
+
+    /// Serializes the source, to be passed to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        match self {
+            InputSource::File(path) => vec!["--input".into(), path.display().to_string()],
+            InputSource::Example(None) => vec!["--example".into()],
+            InputSource::Example(Some(name)) => vec!["--example".into(), name.clone()],
+            InputSource::Stdin => vec!["-".into()],
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SolutionArgsError(String);
+
+impl Display for SolutionArgsError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for SolutionArgsError {}
+
+impl SolutionArgs {
+    pub fn parse(args: Vec<std::ffi::OsString>) -> Result<Self, SolutionArgsError> {
+        let mut args = pico_args::Arguments::from_vec(args);
+        let to_error = |err: pico_args::Error| SolutionArgsError(err.to_string());
+
+        let parsed = Self {
+            submit: args
+                .opt_value_from_fn("--submit", parse_part)
+                .map_err(to_error)?,
+            time: args.contains("--time"),
+            stats: args.contains("--stats"),
+            results: args.contains("--results"),
+            examples: args.contains("--examples"),
+            variants: args.contains("--variants"),
+            input: InputSource::parse(&mut args).map_err(SolutionArgsError)?,
+        };
+
+        parsed.validate()?;
+
+        let remaining = args.finish();
+        if !remaining.is_empty() {
+            return Err(SolutionArgsError(format!(
+                "unknown argument(s): {remaining:?}"
+            )));
+        }
+
+        Ok(parsed)
+    }
+
+    /// Checks combinations of arguments that can not be used together.
+    pub fn validate(&self) -> Result<(), SolutionArgsError> {
+        if self.submit.is_some() && self.input.is_some() {
+            return Err(SolutionArgsError(
+                "only answers for `data/inputs` can be submitted".into(),
+            ));
+        }
+        Ok(())
+    }
+
+    /// Serializes the arguments, to be passed after `--` to `cargo run`.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![];
+        if let Some(part) = self.submit {
+            args.extend(["--submit".into(), part.to_string()]);
+        }
+        for (enabled, flag) in [
+            (self.time, "--time"),
+            (self.stats, "--stats"),
+            (self.results, "--results"),
+            (self.examples, "--examples"),
+            (self.variants, "--variants"),
+        ] {
+            if enabled {
+                args.push(flag.into());
+            }
+        }
+        if let Some(input) = &self.input {
+            args.extend(input.to_args());
+        }
+        args
+    }
+
+    /// The arguments of the running solution binary. Exits if they are invalid.
+    pub fn get() -> &'static Self {
+        static ARGS: OnceLock<SolutionArgs> = OnceLock::new();
+        ARGS.get_or_init(|| {
+            Self::parse(std::env::args_os().skip(1).collect()).unwrap_or_else(|err| {
+                eprintln!("Error: {err}.");
+                eprintln!(
+                    "Usage: cargo solve <day> [--submit <part>] [--input <path> | --example [name] | -]. Solution binaries are run by the `cargo` commands."
+                );
+                std::process::exit(1);
+            })
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{
+        InputSource, SolutionArgs, commands, completions, find_command, has_alias, parse_jobs,
+        parse_part, parse_percent, parse_size, parse_timeout,
+    };
+
+    #[test]
+    fn round_trips_solution_args() {
+        let args = SolutionArgs {
+            submit: Some(2),
+            time: true,
+            stats: true,
+            results: true,
+            ..SolutionArgs::default()
+        };
+        let os_args = args.to_args().into_iter().map(Into::into).collect();
+        assert_eq!(SolutionArgs::parse(os_args).unwrap(), args);
+    }
+
+    #[test]
+    fn validates_solution_args() {
+        assert!(SolutionArgs::parse(vec!["--submit".into(), "3".into()]).is_err());
+        assert!(SolutionArgs::parse(vec!["--submit".into()]).is_err());
+        assert!(SolutionArgs::parse(vec!["--foo".into()]).is_err());
+        assert_eq!(
+            SolutionArgs::parse(vec![]).unwrap(),
+            SolutionArgs::default()
+        );
+    }
+
+    #[test]
+    fn parses_input_sources() {
+        let parse = |args: &[&str]| {
+            SolutionArgs::parse(args.iter().map(Into::into).collect()).map(|args| args.input)
+        };
+
+        assert_eq!(parse(&["-"]).unwrap(), Some(InputSource::Stdin));
+        assert_eq!(parse(&["--input", "-"]).unwrap(), Some(InputSource::Stdin));
+        assert_eq!(
+            parse(&["--input", "big.txt"]).unwrap(),
+            Some(InputSource::File("big.txt".into()))
+        );
+        assert_eq!(
+            parse(&["--example"]).unwrap(),
+            Some(InputSource::Example(None))
+        );
+        assert_eq!(
+            parse(&["--example", "second", "--time"]).unwrap(),
+            Some(InputSource::Example(Some("second".into())))
+        );
+        assert!(parse(&["--example", "-", "--input", "a.txt"]).is_err());
+        assert!(parse(&["--submit", "1", "-"]).is_err());
+        assert!(parse(&["foo"]).is_err());
+
+        let args = SolutionArgs {
+            input: Some(InputSource::Example(Some("second".into()))),
+            ..SolutionArgs::default()
+        };
+        let os_args = args.to_args().into_iter().map(Into::into).collect();
+        assert_eq!(SolutionArgs::parse(os_args).unwrap(), args);
+    }
+
+    #[test]
+    fn validates_values() {
+        assert_eq!(parse_part("1"), Ok(1));
+        assert!(parse_part("0").is_err());
+        assert_eq!(parse_jobs("4"), Ok(4));
+        assert!(parse_jobs("0").is_err());
+        assert_eq!(parse_percent("2.5"), Ok(2.5));
+        assert!(parse_percent("-1").is_err());
+        assert!(parse_percent("fast").is_err());
+    }
+
+    #[test]
+    fn parses_limits() {
+        assert_eq!(parse_timeout("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_timeout("1.5"), Ok(Duration::from_millis(1500)));
+        assert_eq!(parse_timeout("2m"), Ok(Duration::from_secs(120)));
+        assert!(parse_timeout("0s").is_err());
+        assert!(parse_timeout("10 days").is_err());
+
+        assert_eq!(parse_size("4096"), Ok(4096));
+        assert_eq!(parse_size("512M"), Ok(512 * 1024 * 1024));
+        assert_eq!(parse_size("2GiB"), Ok(2 * 1024 * 1024 * 1024));
+        assert!(parse_size("1.5G").is_err());
+        assert!(parse_size("12X").is_err());
+    }
+
+    #[test]
+    fn renders_command_help() {
+        let help = super::command_help(find_command("solve").unwrap());
+        assert!(help.contains("cargo solve <day> [-] [options]"));
+        assert!(help.contains("  --submit <part>   Submit the answer of part 1 or 2."));
+        assert!(find_command("foo").is_none());
+    }
+
+    #[test]
+    fn renders_completions() {
+        for shell in ["bash", "zsh", "fish"] {
+            let script = completions(shell.parse().unwrap());
+            for command in commands() {
+                assert!(script.contains(command.name), "{shell}: {}", command.name);
+            }
+        }
+        let bash = completions("bash".parse().unwrap());
+        assert!(bash.contains("solve:--submit) COMPREPLY=($(compgen -W \"1 2\""));
+        assert!(bash.contains("complete -F _advent_of_code cargo-scaffold cargo-download"));
+        assert!(!bash.contains("cargo-completions"));
+
+        let zsh = completions("zsh".parse().unwrap());
+        assert!(zsh.contains("\n_cargo-solve() { _arguments '--release["));
+        assert!(!zsh.contains("_cargo-stars"));
+
+        let fish = completions("fish".parse().unwrap());
+        assert!(
+            fish.contains("complete -c cargo -f -n '__fish_seen_subcommand_from time' -l store")
+        );
+    }
+
+    #[test]
+    fn finds_cargo_aliases() {
+        let aliases: Vec<&str> = commands()
+            .filter(|command| has_alias(command))
+            .map(|command| command.name)
+            .collect();
+        assert!(aliases.contains(&"solve"));
+        assert!(aliases.contains(&"test-examples"));
+        assert!(!aliases.contains(&"completions"));
+        assert!(!aliases.contains(&"help"));
+    }
+}
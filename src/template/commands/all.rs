@@ -1,5 +1,16 @@
+use std::process;
+
+use crate::template::limits::Limits;
+use crate::template::report::ReportOptions;
 use crate::template::{all_days, run_multi::run_multi};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(is_release: bool, jobs: usize, limits: Limits, reports: ReportOptions) {
+    let (_, report) = run_multi(&all_days().collect(), is_release, false, jobs, &limits);
+
+    println!("\n{}", report.summary());
+    let is_written = reports.write(&report);
+
+    if report.has_failures() || !is_written {
+        process::exit(1);
+    }
 }
@@ -0,0 +1,67 @@
+use std::process;
+
+use crate::template::run_multi::{Error, child_commands};
+use crate::template::variants::{VariantResult, mismatched_parts};
+use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
+
+pub fn handle(day: Day) {
+    let results = match child_commands::run_variants(day) {
+        Ok(Some(results)) => results,
+        Ok(None) => {
+            eprintln!("Day {day} has not been scaffolded yet.");
+            process::exit(1);
+        }
+        Err(Error::Failed(stderr)) => {
+            eprintln!("Failed to run variants:\n{stderr}");
+            process::exit(1);
+        }
+        Err(err) => {
+            eprintln!("Failed to run variants: {err:?}");
+            process::exit(1);
+        }
+    };
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+
+    for part in [1, 2] {
+        let variants: Vec<&VariantResult> = results.iter().filter(|r| r.part == part).collect();
+        if !variants.is_empty() {
+            print_part(part, &variants);
+        }
+    }
+
+    let mismatched = mismatched_parts(&results);
+    println!();
+    if mismatched.is_empty() {
+        println!("All variants agree.");
+    } else {
+        for part in mismatched {
+            eprintln!("Variants of part {part} returned different answers.");
+        }
+        process::exit(1);
+    }
+}
+
+/// Prints the variants of a part by ascending time, relative to the fastest one.
+fn print_part(part: u8, variants: &[&VariantResult]) {
+    let mut variants = variants.to_vec();
+    variants.sort_by_key(|variant| variant.duration);
+
+    let fastest = variants[0].duration.as_nanos().max(1) as f64;
+    let name_width = variants.iter().map(|v| v.name.len()).max().unwrap_or(0);
+    let answers: Vec<String> = variants
+        .iter()
+        .map(|v| v.answer.clone().unwrap_or_else(|| "✖".into()))
+        .collect();
+    let answer_width = answers.iter().map(|a| a.chars().count()).max().unwrap_or(0);
+
+    println!("Part {part}:");
+    for (variant, answer) in variants.iter().zip(&answers) {
+        let relative = variant.duration.as_nanos() as f64 / fastest;
+        println!(
+            "  {:name_width$}  {ANSI_BOLD}{answer:answer_width$}{ANSI_RESET}  {ANSI_ITALIC}{:.1?} @ {} samples{ANSI_RESET}  {relative:.2}x",
+            variant.name, variant.duration, variant.samples,
+        );
+    }
+}
@@ -0,0 +1,120 @@
+use std::{fs, path::PathBuf, process};
+
+use crate::template::Day;
+use crate::template::examples::Example;
+use crate::template::puzzle::Puzzle;
+
+/// Writes the example input and answers from the puzzle description of `day` to `data/examples/NN/example.txt`.
+/// An example file without input, such as the one created by `scaffold`, is filled in. Answers that were
+/// declared in it by hand are kept if the puzzle does not emphasize one, other parameters are always kept.
+///
+/// # Errors
+/// Returns a message for the user if the puzzle has no example, or if `overwrite` is not set and the example
+/// file already has an input or the day has examples under other names.
+pub fn extract(day: Day, overwrite: bool) -> Result<(PathBuf, [Option<String>; 2]), String> {
+    let Some(puzzle) = Puzzle::read(day) else {
+        return Err(format!(
+            "Could not read the puzzle of day {day}. Run `cargo download {day}` first."
+        ));
+    };
+    if puzzle.example_input.is_none() {
+        return Err(format!(
+            "Could not find an example in the puzzle of day {day}."
+        ));
+    }
+
+    let dir = PathBuf::from("data").join("examples").join(day.to_string());
+    let path = dir.join("example.txt");
+
+    let has_other_examples = !path.exists()
+        && fs::read_dir(&dir).is_ok_and(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .any(|entry| entry.path().extension().is_some_and(|ext| ext == "txt"))
+        });
+    if has_other_examples && !overwrite {
+        return Err(format!(
+            "\"{}\" already has examples. Pass --overwrite to add \"example.txt\" next to them.",
+            dir.display()
+        ));
+    }
+
+    let existing = fs::read_to_string(&path)
+        .ok()
+        .map(|contents| Example::from_file_contents("example", &contents));
+    if let Some(existing) = &existing
+        && !existing.input.trim().is_empty()
+        && !overwrite
+    {
+        return Err(format!(
+            "\"{}\" already has an input. Pass --overwrite to replace it.",
+            path.display()
+        ));
+    }
+
+    let (contents, answers) = example_file(puzzle, existing.as_ref());
+    fs::create_dir_all(&dir)
+        .and_then(|()| fs::write(&path, contents))
+        .map_err(|err| format!("Failed to write \"{}\": {err}", path.display()))?;
+
+    Ok((path, answers))
+}
+
+/// Renders the example of `puzzle`, merged with the answers and parameters of the `existing` example file.
+fn example_file(mut puzzle: Puzzle, existing: Option<&Example>) -> (String, [Option<String>; 2]) {
+    let Some(existing) = existing else {
+        return (puzzle.example_file(&[]), puzzle.example_answers);
+    };
+
+    for (answer, part) in puzzle.example_answers.iter_mut().zip(1..) {
+        if answer.is_none() {
+            *answer = existing.expected(part).map(String::from);
+        }
+    }
+    (
+        puzzle.example_file(&existing.context.params()),
+        puzzle.example_answers,
+    )
+}
+
+pub fn handle(day: Day, overwrite: bool) {
+    match extract(day, overwrite) {
+        Ok((path, answers)) => {
+            println!("Wrote example to \"{}\"", path.display());
+            for (answer, part) in answers.iter().zip(1..) {
+                println!(
+                    "  Part {part}: {}",
+                    answer.as_deref().unwrap_or("no answer found")
+                );
+            }
+        }
+        Err(err) => {
+            eprintln!("{err}");
+            process::exit(1);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::example_file;
+    use crate::template::examples::Example;
+    use crate::template::puzzle::Puzzle;
+
+    const MARKDOWN: &str = include_str!("../fixtures/puzzle.md");
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    #[test]
+    fn keeps_parameters_of_existing_example() {
+        let (part_one, _) = MARKDOWN.split_once("Your puzzle").unwrap();
+        let puzzle = Puzzle::parse(part_one);
+        let existing = Example::from_file_contents("example", EXAMPLE);
+
+        let (contents, answers) = example_file(puzzle, Some(&existing));
+        assert_eq!(answers, [Some("3".into()), Some("5".into())]);
+        assert_eq!(
+            contents,
+            "---\npart_1: 3\npart_2: 5\nconnections: 10\nname: small\n---\n199\n200\n208\n210\n200\n"
+        );
+    }
+}
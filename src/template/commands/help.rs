@@ -0,0 +1,17 @@
+use std::process;
+
+use crate::template::cli;
+
+/// Prints the list of commands, or the usage and options of `command`.
+pub fn handle(command: Option<&str>) {
+    match command {
+        None => println!("{}", cli::help()),
+        Some(name) => match cli::find_command(name) {
+            Some(command) => println!("{}", cli::command_help(command)),
+            None => {
+                eprintln!("Unknown command: {name}\n\n{}", cli::help());
+                process::exit(1);
+            }
+        },
+    }
+}
@@ -0,0 +1,19 @@
+use std::{fs, process};
+
+use crate::template::history::History;
+
+/// Exports the benchmark history as CSV, to `out` if given or to stdout otherwise.
+pub fn handle(out: Option<String>) {
+    let csv = History::read_from_file().to_csv();
+
+    match out {
+        Some(path) => {
+            if let Err(err) = fs::write(&path, csv) {
+                eprintln!("Failed to write benchmark history to \"{path}\": {err}");
+                process::exit(1);
+            }
+            println!("Exported benchmark history to \"{path}\".");
+        }
+        None => print!("{csv}"),
+    }
+}
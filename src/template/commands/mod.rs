@@ -1,6 +1,13 @@
 pub mod all;
+pub mod compare;
+pub mod completions;
 pub mod download;
+pub mod example;
+pub mod help;
+pub mod history;
 pub mod read;
 pub mod scaffold;
 pub mod solve;
+pub mod stars;
+pub mod test_examples;
 pub mod time;
@@ -1,17 +1,55 @@
-use std::process;
+use std::{
+    env,
+    io::{self, IsTerminal, Write},
+    process::{self, Command, Stdio},
+};
 
-use crate::template::{Day, aoc_cli};
+use crate::template::{Day, markdown, puzzle};
 
-pub fn handle(day: Day) {
-    if aoc_cli::check().is_err() {
-        eprintln!(
-            "command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it."
-        );
+pub fn handle(day: Day, no_pager: bool) {
+    let Some(description) = puzzle::read_markdown(day) else {
+        eprintln!("Could not read the puzzle of day {day}. Run `cargo download {day}` first.");
         process::exit(1);
+    };
+
+    let is_terminal = io::stdout().is_terminal();
+    let text = markdown::render(&description, terminal_width(), is_terminal);
+    if no_pager || !is_terminal || !page(&text) {
+        print!("{text}");
     }
+}
 
-    if let Err(e) = aoc_cli::read(day) {
-        eprintln!("failed to call aoc-cli: {e}");
-        process::exit(1);
+/// The width to wrap paragraphs at, from `COLUMNS`. Capped to keep long lines readable.
+fn terminal_width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|columns| columns.parse().ok())
+        .unwrap_or(80)
+        .clamp(40, 100)
+}
+
+/// Shows `text` in `$PAGER`, or in `less`. Returns `false` if no pager could be started.
+fn page(text: &str) -> bool {
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less".into());
+    let mut args = pager.split_whitespace();
+    let Some(program) = args.next() else {
+        return false;
     };
+
+    let mut command = Command::new(program);
+    command.args(args).stdin(Stdio::piped());
+    if env::var_os("LESS").is_none() {
+        // keep colors, and quit right away if the text fits on the screen.
+        command.env("LESS", "-FRX");
+    }
+
+    let Ok(mut child) = command.spawn() else {
+        return false;
+    };
+    if let Some(mut stdin) = child.stdin.take() {
+        // the pipe breaks if the pager is closed before the end, which is not an error.
+        let _ = stdin.write_all(text.as_bytes());
+    }
+    let _ = child.wait();
+    true
 }
@@ -1,79 +1,246 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs, io,
+    path::{Path, PathBuf},
     process,
 };
 
 use crate::template::Day;
+use crate::template::aoc_cli::get_year;
+use crate::template::templates::{self, Placeholders};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Action {
+    Create,
+    Keep,
+    Overwrite,
+}
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
+/// A file that scaffolding creates, keeps or overwrites. All operations are planned before any file is written.
+#[derive(Debug)]
+struct Operation {
+    /// Path relative to the project root.
+    path: PathBuf,
+    kind: &'static str,
+    contents: String,
+    /// Whether the new file is a placeholder without content for the user.
+    is_empty: bool,
+    action: Action,
+}
+
+impl Operation {
+    /// Plans to write `contents` to `path`. An existing file is only replaced if `replace` is set.
+    fn new(root: &Path, path: String, kind: &'static str, contents: String, replace: bool) -> Self {
+        let path = PathBuf::from(path);
+        let action = match (root.join(&path).exists(), replace) {
+            (false, _) => Action::Create,
+            (true, true) => Action::Overwrite,
+            (true, false) => Action::Keep,
+        };
+
+        Self {
+            path,
+            kind,
+            contents,
+            is_empty: false,
+            action,
+        }
+    }
+
+    fn empty(mut self, is_empty: bool) -> Self {
+        self.is_empty = is_empty;
+        self
+    }
+
+    fn describe(&self, dry_run: bool) -> String {
+        let verb = match (self.action, dry_run) {
+            (Action::Create, false) => "Created",
+            (Action::Create, true) => "Would create",
+            (Action::Keep, false) => "Kept existing",
+            (Action::Keep, true) => "Would keep existing",
+            (Action::Overwrite, false) => "Overwrote",
+            (Action::Overwrite, true) => "Would overwrite",
+        };
+        let empty = if self.is_empty && self.action != Action::Keep {
+            "empty "
+        } else {
+            ""
+        };
+        format!("{verb} {empty}{} \"{}\"", self.kind, self.path.display())
+    }
+
+    fn execute(&self, root: &Path) -> io::Result<()> {
+        if self.action == Action::Keep {
+            return Ok(());
+        }
+        let path = root.join(&self.path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, &self.contents)
     }
-    file.truncate(true).write(true).open(path)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// The first example file of `day` relative to `root`, sorted by name, if the day has any examples.
+fn existing_example(root: &Path, day: Day) -> Option<String> {
+    let dir = format!("data/examples/{day}");
+    let mut names: Vec<String> = fs::read_dir(root.join(&dir))
+        .ok()?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .filter_map(|path| Some(path.file_name()?.to_str()?.to_string()))
+        .collect();
+    names.sort_unstable();
+    names.first().map(|name| format!("{dir}/{name}"))
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Plans the files of a day. Inputs and examples are never replaced, the module only with `overwrite`.
+/// A day that has examples under other names than `example.txt` keeps them instead of getting a new one.
+fn plan(root: &Path, module: &str, placeholders: &Placeholders, overwrite: bool) -> Vec<Operation> {
+    let day = placeholders.day;
+    let puzzle = &placeholders.puzzle;
+
+    vec![
+        Operation::new(
+            root,
+            format!("src/bin/{day}.rs"),
+            "module file",
+            placeholders.render(module),
+            overwrite,
+        ),
+        Operation::new(
+            root,
+            format!("data/inputs/{day}.txt"),
+            "input file",
+            String::new(),
+            false,
+        )
+        .empty(true),
+        Operation::new(
+            root,
+            existing_example(root, day)
+                .unwrap_or_else(|| format!("data/examples/{day}/example.txt")),
+            "example file",
+            puzzle.example_file(&[]),
+            false,
+        )
+        .empty(puzzle.example_input.is_none()),
+    ]
+}
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
+pub fn handle(day: Day, overwrite: bool, dry_run: bool, template: &str) {
+    let module = match templates::load(template) {
+        Ok(template) => template,
         Err(e) => {
-            eprintln!("Failed to create module file: {e}");
+            eprintln!("{e}");
             process::exit(1);
         }
     };
+    let placeholders = Placeholders::new(day, get_year());
+    let root = Path::new(".");
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
+    for operation in plan(root, &module, &placeholders, overwrite) {
+        if !dry_run && let Err(e) = operation.execute(root) {
+            eprintln!(
+                "Failed to write {} \"{}\": {e}",
+                operation.kind,
+                operation.path.display()
+            );
             process::exit(1);
         }
+        println!("{}", operation.describe(dry_run));
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
-        }
+    println!("---");
+    if dry_run {
+        println!("Dry run, no files were changed.");
+    } else {
+        println!("🎄 Type `cargo solve {day}` to run your solution.");
     }
+}
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs, process};
+
+    use super::{Action, plan};
+    use crate::day;
+    use crate::template::puzzle::Puzzle;
+    use crate::template::templates::Placeholders;
+
+    #[test]
+    fn keeps_existing_files() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("data/inputs")).unwrap();
+        fs::write(root.join("data/inputs/05.txt"), "123\n").unwrap();
+
+        let placeholders = Placeholders {
+            day: day!(5),
+            year: None,
+            puzzle: Puzzle::default(),
+        };
+        let module = "solution!(%DAY_NUMBER%);";
+
+        let operations = plan(&root, module, &placeholders, false);
+        let actions: Vec<Action> = operations.iter().map(|o| o.action).collect();
+        assert_eq!(actions, [Action::Create, Action::Keep, Action::Create]);
+        assert_eq!(
+            operations[0].describe(true),
+            "Would create module file \"src/bin/05.rs\""
+        );
+        assert_eq!(
+            operations[1].describe(false),
+            "Kept existing input file \"data/inputs/05.txt\""
+        );
+        assert_eq!(
+            operations[2].describe(false),
+            "Created empty example file \"data/examples/05/example.txt\""
+        );
+
+        for operation in &operations {
+            operation.execute(&root).unwrap();
         }
+        assert_eq!(
+            fs::read_to_string(root.join("src/bin/05.rs")).unwrap(),
+            "solution!(5);"
+        );
+        assert_eq!(
+            fs::read_to_string(root.join("data/inputs/05.txt")).unwrap(),
+            "123\n"
+        );
+
+        let actions: Vec<Action> = plan(&root, module, &placeholders, true)
+            .iter()
+            .map(|o| o.action)
+            .collect();
+        assert_eq!(actions, [Action::Overwrite, Action::Keep, Action::Keep]);
+
+        fs::remove_dir_all(&root).unwrap();
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    #[test]
+    fn keeps_examples_with_other_names() {
+        let root = env::temp_dir().join(format!("aoc-scaffold-examples-{}", process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("data/examples/11")).unwrap();
+        fs::write(root.join("data/examples/11/part_2.txt"), "b\n").unwrap();
+        fs::write(root.join("data/examples/11/part_1.txt"), "a\n").unwrap();
+        fs::write(root.join("data/examples/11/notes.md"), "").unwrap();
+
+        let placeholders = Placeholders {
+            day: day!(11),
+            year: None,
+            puzzle: Puzzle::default(),
+        };
+        let operations = plan(&root, "", &placeholders, true);
+        assert_eq!(operations[2].action, Action::Keep);
+        assert_eq!(
+            operations[2].describe(true),
+            "Would keep existing example file \"data/examples/11/part_1.txt\""
+        );
+        assert!(!root.join("data/examples/11/example.txt").exists());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
 }
@@ -1,8 +1,49 @@
-use std::process::{Command, Stdio};
+use std::process::{self, Command, ExitStatus, Stdio};
 
-use crate::template::Day;
+use crate::template::cli::{InputSource, SolutionArgs};
+use crate::template::commands::test_examples;
+use crate::template::watch::{self, WatchPath};
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Day};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
+pub fn handle(day: Day, release: bool, dhat: bool, watch: bool, solution_args: &SolutionArgs) {
+    if watch {
+        watch_day(day, release, dhat, solution_args);
+    }
+
+    // pass on failures, e.g. a part returning `None` or a wrong answer, to scripts and CI.
+    let status = run(day, release, dhat, solution_args);
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
+
+/// Re-runs the examples and the solution of a day whenever its sources or data change.
+fn watch_day(day: Day, release: bool, dhat: bool, solution_args: &SolutionArgs) -> ! {
+    let mut paths = watch::paths_for_day(day);
+    if let Some(InputSource::File(path)) = &solution_args.input {
+        paths.push(WatchPath::File(path.clone()));
+    }
+
+    let mut snapshot = watch::snapshot(&paths);
+
+    loop {
+        test_examples::check_day(day, release, &mut false);
+        println!();
+        run(day, release, dhat, solution_args);
+
+        println!("\n{ANSI_ITALIC}Watching for changes, press Ctrl-C to stop.{ANSI_RESET}");
+        let (next, changed) = watch::wait_for_change(&paths, &snapshot);
+        snapshot = next;
+
+        println!();
+        for path in changed {
+            println!("{ANSI_ITALIC}Changed: {}{ANSI_RESET}", path.display());
+        }
+        println!();
+    }
+}
+
+fn run(day: Day, release: bool, dhat: bool, solution_args: &SolutionArgs) -> ExitStatus {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if dhat {
@@ -17,11 +58,7 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     }
 
     cmd_args.push("--".to_string());
-
-    if let Some(submit_part) = submit_part {
-        cmd_args.push("--submit".to_string());
-        cmd_args.push(submit_part.to_string());
-    }
+    cmd_args.extend(solution_args.to_args());
 
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
@@ -30,5 +67,5 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    cmd.wait().unwrap()
 }
@@ -0,0 +1,29 @@
+use std::process;
+
+use crate::template::answers::Answers;
+use crate::template::readme_stars;
+
+/// Regenerates the stars table of the readme from the recorded answers.
+pub fn handle() {
+    let answers = match Answers::read_from_file() {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("Failed to update stars table: {err}");
+            process::exit(1);
+        }
+    };
+
+    match readme_stars::update(&answers) {
+        Ok(stars) => {
+            let count: usize = stars
+                .values()
+                .map(|parts| parts.iter().filter(|&&solved| solved).count())
+                .sum();
+            println!("Updated stars table with {count} ⭐.");
+        }
+        Err(err) => {
+            eprintln!("Failed to update stars table: {err:?}");
+            process::exit(1);
+        }
+    }
+}
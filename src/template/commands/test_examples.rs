@@ -0,0 +1,94 @@
+use std::process;
+
+use crate::template::examples::ExampleResult;
+use crate::template::run_multi::{Error, child_commands};
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, all_days};
+
+pub fn handle(day: Option<Day>, is_release: bool) {
+    let days: Vec<Day> = day.map_or_else(|| all_days().collect(), |day| vec![day]);
+
+    let mut passed = 0;
+    let mut failed = 0;
+    let mut need_space = false;
+
+    for day in days {
+        if let Some((day_passed, day_failed)) = check_day(day, is_release, &mut need_space) {
+            passed += day_passed;
+            failed += day_failed;
+        }
+    }
+
+    println!("\n{ANSI_BOLD}Examples:{ANSI_RESET} {passed} passed, {failed} failed");
+
+    if failed > 0 {
+        process::exit(1);
+    }
+}
+
+/// Runs and prints the examples of a day.
+/// Returns the number of passed and failed examples, or `None` if the day has no examples.
+pub(crate) fn check_day(
+    day: Day,
+    is_release: bool,
+    need_space: &mut bool,
+) -> Option<(usize, usize)> {
+    let results = match child_commands::run_examples(day, is_release) {
+        Ok(Some(results)) if !results.is_empty() => results,
+        Ok(_) => return None,
+        Err(err) => {
+            print_day_header(day, need_space);
+            match err {
+                Error::Failed(stderr) => eprintln!("Failed to run examples:\n{stderr}"),
+                err => eprintln!("Failed to run examples: {err:?}"),
+            }
+            return Some((0, 1));
+        }
+    };
+
+    print_day_header(day, need_space);
+    print_matrix(&results);
+
+    for result in results.iter().filter(|result| !result.passed()) {
+        println!(
+            "  {} part {}: expected {}, got {}",
+            result.example,
+            result.part,
+            result.expected,
+            result.actual.as_deref().unwrap_or("None")
+        );
+    }
+
+    let passed = results.iter().filter(|result| result.passed()).count();
+    Some((passed, results.len() - passed))
+}
+
+fn print_day_header(day: Day, need_space: &mut bool) {
+    if *need_space {
+        println!();
+    }
+    *need_space = true;
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Prints one row per example and one column per part. Parts without an expected answer are left blank.
+fn print_matrix(results: &[ExampleResult]) {
+    let mut examples: Vec<&str> = results.iter().map(|r| r.example.as_str()).collect();
+    examples.sort_unstable();
+    examples.dedup();
+
+    let width = examples.iter().map(|name| name.len()).max().unwrap_or(0);
+    println!("{:width$}  Part 1  Part 2", "");
+
+    for example in examples {
+        let cells = [1, 2].map(|part| {
+            results
+                .iter()
+                .find(|r| r.example == example && r.part == part)
+                .map_or(" ", |r| if r.passed() { "✔" } else { "✖" })
+        });
+        let row = format!("{example:width$}  {:6}  {}", cells[0], cells[1]);
+        println!("{}", row.trim_end());
+    }
+}
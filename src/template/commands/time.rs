@@ -1,15 +1,58 @@
 use std::collections::HashSet;
+use std::{fs, process};
 
-use crate::template::run_multi::run_multi;
+use crate::template::export::{self, ExportFormat};
+use crate::template::history::{self, History, RunInfo};
+use crate::template::limits::Limits;
+use crate::template::report::ReportOptions;
+use crate::template::run_multi::{Error, child_commands, for_each_ordered, run_multi};
 use crate::template::timings::Timings;
-use crate::template::{Day, all_days, readme_benchmarks};
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day, all_days, readme_benchmarks};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Options of `cargo time --compare`.
+pub struct CompareOptions {
+    /// Tag or commit prefix of the run to compare against. Defaults to the most recent run.
+    pub baseline: Option<String>,
+    /// Slowdown in percent above which a day is reported as a regression.
+    pub threshold: f64,
+}
+
+/// Options of `cargo time --export`.
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    pub out: String,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    day: Option<Day>,
+    run_all: bool,
+    store: bool,
+    dhat: bool,
+    tag: Option<String>,
+    compare: Option<CompareOptions>,
+    export: Option<ExportOptions>,
+    jobs: usize,
+    limits: Limits,
+    reports: ReportOptions,
+) {
+    let stored_timings = match Timings::read_from_file() {
+        Ok(timings) => timings,
+        Err(err) if store => {
+            eprintln!(
+                "Failed to read stored benchmarks: {err} Fix or remove the file to store benchmarks."
+            );
+            process::exit(1);
+        }
+        Err(err) => {
+            eprintln!("warning: {err} Stored benchmarks are ignored.");
+            Timings::default()
+        }
+    };
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
+            if run_all || compare.is_some() {
                 all_days().collect()
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
@@ -21,12 +64,37 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    // benches always run sequentially, `jobs` only applies to the memory profiling pass.
+    let (timings, report) = run_multi(&days_to_run, true, true, 1, &limits);
+    let mut timings = timings.unwrap();
+    if dhat {
+        profile_memory(&mut timings, jobs);
+    }
+    let run = RunInfo::current("release", tag);
+
+    let has_regressions =
+        compare.is_some_and(|options| report_comparison(&run, &timings, &options));
+
+    let merged_timings = stored_timings.merge(&timings);
+
+    if let Some(options) = export {
+        let artifact = export::render(options.format, &merged_timings);
+        match fs::write(&options.out, artifact) {
+            Ok(()) => println!("\nExported benchmarks to \"{}\".", options.out),
+            Err(err) => {
+                eprintln!("Failed to export benchmarks to \"{}\": {err}", options.out);
+                process::exit(1);
+            }
+        }
+    }
 
     if store {
-        let merged_timings = stored_timings.merge(&timings);
         merged_timings.store_file().unwrap();
 
+        if let Err(err) = History::append_to_file(&run, &timings) {
+            eprintln!("Failed to append to benchmark history: {err}");
+        }
+
         println!();
         match readme_benchmarks::update(merged_timings) {
             Ok(()) => {
@@ -37,4 +105,95 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    println!("\n{}", report.summary());
+    let is_written = reports.write(&report);
+
+    if has_regressions || report.has_failures() || !is_written {
+        process::exit(1);
+    }
+}
+
+/// Runs every timed day once more with dhat and attaches the memory statistics to its parts.
+/// This is a separate pass, as the dhat allocator would skew the benchmarks.
+/// Heap statistics do not depend on timing, so up to `jobs` days are profiled concurrently.
+fn profile_memory(timings: &mut Timings, jobs: usize) {
+    println!("\n{ANSI_BOLD}Memory{ANSI_RESET}");
+    println!("------");
+
+    let days: Vec<(usize, Day)> = timings.data.iter().map(|t| t.day).enumerate().collect();
+
+    for_each_ordered(
+        days,
+        jobs,
+        |(index, day)| (index, day, child_commands::run_memory_profile(day)),
+        |(index, day, result)| {
+            let stats = match result {
+                Ok(Some(stats)) => stats,
+                Ok(None) => return,
+                Err(Error::Failed(stderr)) => {
+                    eprintln!("Failed to profile day {day}:\n{stderr}");
+                    return;
+                }
+                Err(err) => {
+                    eprintln!("Failed to profile day {day}: {err:?}");
+                    return;
+                }
+            };
+
+            let timing = &mut timings.data[index];
+            for (part, memory) in stats {
+                let part_timing = match part {
+                    1 => timing.part_1.as_mut(),
+                    2 => timing.part_2.as_mut(),
+                    _ => None,
+                };
+                if let Some(part_timing) = part_timing {
+                    println!("Day {day} Part {part}: {}", memory.format());
+                    part_timing.memory = Some(memory);
+                }
+            }
+        },
+    );
+}
+
+/// Prints the change of each day against its baseline. Returns whether any day regressed.
+fn report_comparison(run: &RunInfo, timings: &Timings, options: &CompareOptions) -> bool {
+    let history = History::read_from_file();
+    let comparisons = history::compare(&history, run, timings, options.baseline.as_deref());
+
+    println!("\n{ANSI_BOLD}Comparison{ANSI_RESET}");
+    println!("----------");
+
+    if comparisons.is_empty() {
+        println!("No baseline found in the benchmark history for this machine.");
+        return false;
+    }
+
+    let mut has_regressions = false;
+    for comparison in &comparisons {
+        let baseline = &comparison.baseline.run;
+        let is_regression = comparison.is_regression(options.threshold);
+        has_regressions |= is_regression;
+
+        println!(
+            "Day {}: {:.2}ms -> {:.2}ms ({:+.1}%) vs. {} from {}{}",
+            comparison.day,
+            comparison.baseline.total_nanos / 1_000_000_f64,
+            comparison.total_nanos / 1_000_000_f64,
+            comparison.change_percent(),
+            baseline
+                .tag
+                .as_deref()
+                .or(baseline.commit.as_deref())
+                .unwrap_or("unknown commit"),
+            history::format_timestamp(baseline.timestamp),
+            if is_regression { " ✖ regression" } else { "" }
+        );
+    }
+
+    if has_regressions {
+        println!("\nSome days regressed by more than {}%.", options.threshold);
+    }
+    has_regressions
 }
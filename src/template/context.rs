@@ -0,0 +1,176 @@
+/// Information about the input that a solution part is run against.
+use std::{collections::HashMap, str::FromStr};
+
+static FRONT_MATTER_DELIMITER: &str = "---";
+
+/// Describes the input a part is run against: whether it is an example and which named
+/// parameters were declared for it.
+///
+/// Parameters are declared in an optional front matter header at the top of an input file:
+///
+/// ```text
+/// ---
+/// connections: 10
+/// ---
+/// 162,817,812
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Context {
+    is_example: bool,
+    params: HashMap<String, String>,
+}
+
+impl Context {
+    pub fn new(is_example: bool) -> Self {
+        Self {
+            is_example,
+            params: HashMap::new(),
+        }
+    }
+
+    /// Adds or replaces the parameter `name`.
+    #[must_use]
+    pub fn with_param(mut self, name: &str, value: &str) -> Self {
+        self.params.insert(name.into(), value.into());
+        self
+    }
+
+    /// Removes the parameter `name` and returns its raw value.
+    pub(crate) fn take_param(&mut self, name: &str) -> Option<String> {
+        self.params.remove(name)
+    }
+
+    /// All parameters, sorted by name.
+    pub(crate) fn params(&self) -> Vec<(&str, &str)> {
+        let mut params: Vec<(&str, &str)> = self
+            .params
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        params.sort_unstable();
+        params
+    }
+
+    pub fn is_example(&self) -> bool {
+        self.is_example
+    }
+
+    /// Returns the parameter `name` parsed as `T`, or `None` if it was not declared.
+    ///
+    /// # Panics
+    /// Panics if the parameter was declared but can not be parsed as `T`.
+    pub fn param<T: FromStr>(&self, name: &str) -> Option<T> {
+        let value = self.params.get(name)?;
+        match value.parse() {
+            Ok(value) => Some(value),
+            Err(_) => panic!("could not parse parameter `{name}` with value \"{value}\""),
+        }
+    }
+
+    /// Returns the parameter `name` parsed as `T`, or `default` if it was not declared.
+    pub fn param_or<T: FromStr>(&self, name: &str, default: T) -> T {
+        self.param(name).unwrap_or(default)
+    }
+
+    /// Splits the front matter off `contents` and returns the context it declares along with the remaining input.
+    pub fn from_file_contents(contents: &str, is_example: bool) -> (Self, &str) {
+        let mut context = Self::new(is_example);
+
+        let Some(rest) = strip_delimiter_line(contents) else {
+            return (context, contents);
+        };
+
+        let mut remaining = rest;
+        while let Some((line, tail)) = remaining.split_once('\n') {
+            let line = line.trim_end_matches('\r');
+            if line == FRONT_MATTER_DELIMITER {
+                return (context, tail);
+            }
+            if let Some((key, value)) = line.split_once(':') {
+                context = context.with_param(key.trim(), value.trim());
+            }
+            remaining = tail;
+        }
+
+        // An unterminated header is not front matter, treat the whole file as input.
+        (Self::new(is_example), contents)
+    }
+}
+
+fn strip_delimiter_line(contents: &str) -> Option<&str> {
+    let rest = contents.strip_prefix(FRONT_MATTER_DELIMITER)?;
+    rest.strip_prefix('\n')
+        .or_else(|| rest.strip_prefix("\r\n"))
+}
+
+/// Marker for parts with the signature `fn(&str) -> Option<T>`.
+pub struct WithoutContext;
+
+/// Marker for parts with the signature `fn(&str, &Context) -> Option<T>`.
+pub struct WithContext;
+
+/// Implemented by solution parts, which may optionally receive the [`Context`] as a second argument.
+pub trait Part<Marker, T> {
+    fn call(&self, input: &str, context: &Context) -> Option<T>;
+}
+
+impl<F, T> Part<WithoutContext, T> for F
+where
+    F: Fn(&str) -> Option<T>,
+{
+    fn call(&self, input: &str, _context: &Context) -> Option<T> {
+        self(input)
+    }
+}
+
+impl<F, T> Part<WithContext, T> for F
+where
+    F: Fn(&str, &Context) -> Option<T>,
+{
+    fn call(&self, input: &str, context: &Context) -> Option<T> {
+        self(input, context)
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Context;
+
+    #[test]
+    fn parses_front_matter() {
+        let (context, input) =
+            Context::from_file_contents("---\nconnections: 10\nname:  foo \n---\n1,2,3\n", true);
+        assert_eq!(input, "1,2,3\n");
+        assert!(context.is_example());
+        assert_eq!(context.param::<usize>("connections"), Some(10));
+        assert_eq!(context.param::<String>("name"), Some("foo".into()));
+        assert_eq!(context.param_or("missing", 5), 5);
+    }
+
+    #[test]
+    fn handles_missing_front_matter() {
+        let (context, input) = Context::from_file_contents("1,2,3\n---\n", false);
+        assert_eq!(input, "1,2,3\n---\n");
+        assert_eq!(context, Context::new(false));
+    }
+
+    #[test]
+    fn handles_unterminated_front_matter() {
+        let (context, input) = Context::from_file_contents("---\na: 1\n", true);
+        assert_eq!(input, "---\na: 1\n");
+        assert_eq!(context.param::<u8>("a"), None);
+    }
+
+    #[test]
+    fn handles_crlf_front_matter() {
+        let (context, input) = Context::from_file_contents("---\r\na: 1\r\n---\r\nx", true);
+        assert_eq!(input, "x");
+        assert_eq!(context.param::<u8>("a"), Some(1));
+    }
+
+    #[test]
+    #[should_panic(expected = "could not parse parameter `a`")]
+    fn panics_for_unparsable_params() {
+        Context::new(true).with_param("a", "x").param::<u8>("a");
+    }
+}
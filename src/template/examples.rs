@@ -0,0 +1,306 @@
+/// Loads the examples of a day and checks solution parts against their expected answers.
+///
+/// Examples live in `data/examples/NN/<name>.txt`. The expected answers are declared in the
+/// front matter of each file, next to the example's parameters:
+///
+/// ```text
+/// ---
+/// part_1: 40
+/// part_2: 25272
+/// ---
+/// 162,817,812
+/// ```
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Write},
+    path::PathBuf,
+};
+
+use crate::template::{Context, Day};
+
+/// A single example input of a day.
+#[derive(Debug, Clone)]
+pub struct Example {
+    pub name: String,
+    pub input: String,
+    pub context: Context,
+    expected: [Option<String>; 2],
+}
+
+impl Example {
+    /// Parses an example file. Empty answers in the front matter count as not declared.
+    pub fn from_file_contents(name: &str, contents: &str) -> Self {
+        let (mut context, input) = Context::from_file_contents(contents, true);
+        let expected = [1, 2].map(|part| {
+            context
+                .take_param(&expected_key(part))
+                .filter(|answer| !answer.is_empty())
+        });
+
+        Self {
+            name: name.into(),
+            input: input.into(),
+            context,
+            expected,
+        }
+    }
+
+    /// The expected answer for `part`, if one was declared.
+    pub fn expected(&self, part: u8) -> Option<&str> {
+        self.expected
+            .get(usize::from(part).checked_sub(1)?)?
+            .as_deref()
+    }
+}
+
+fn expected_key(part: u8) -> String {
+    format!("part_{part}")
+}
+
+#[must_use]
+pub fn get_examples_dir(day: Day) -> PathBuf {
+    env::current_dir()
+        .unwrap()
+        .join("data")
+        .join("examples")
+        .join(day.to_string())
+}
+
+/// Reads all examples of `day`, sorted by name. Returns an empty list if the day has no examples.
+#[must_use]
+pub fn read_examples(day: Day) -> Vec<Example> {
+    let Ok(entries) = fs::read_dir(get_examples_dir(day)) else {
+        return vec![];
+    };
+
+    let mut examples: Vec<Example> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+        .map(|path| {
+            let name = path.file_stem().unwrap().to_string_lossy().to_string();
+            let contents = fs::read_to_string(&path).expect("could not open example file");
+            Example::from_file_contents(&name, &contents)
+        })
+        .collect();
+
+    examples.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+    examples
+}
+
+/// Reads the example `name` of `day` and returns its input along with its [`Context`].
+#[must_use]
+pub fn read_example(day: Day, name: &str) -> (String, Context) {
+    let path = get_examples_dir(day).join(format!("{name}.txt"));
+    let contents = fs::read_to_string(path).expect("could not open example file");
+    let example = Example::from_file_contents(name, &contents);
+    (example.input, example.context)
+}
+
+/// The outcome of running one part against one example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExampleResult {
+    pub example: String,
+    pub part: u8,
+    pub expected: String,
+    pub actual: Option<String>,
+}
+
+static RESULT_PREFIX: &str = "Example result:";
+/// Stands for a part returning `None`. Can not be produced by escaping an answer.
+pub(crate) static NONE_MARKER: &str = "\\-";
+
+impl ExampleResult {
+    pub fn passed(&self) -> bool {
+        self.actual.as_ref() == Some(&self.expected)
+    }
+
+    /// Serializes the result to a single line, so it can be passed from a solution binary to the runner.
+    pub fn to_line(&self) -> String {
+        format!(
+            "{RESULT_PREFIX}\t{}\t{}\t{}\t{}",
+            escape(&self.example),
+            self.part,
+            escape(&self.expected),
+            self.actual
+                .as_deref()
+                .map_or_else(|| NONE_MARKER.into(), escape)
+        )
+    }
+
+    /// Parses a line written by [`ExampleResult::to_line`]. Returns `None` for any other line.
+    pub fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line
+            .strip_prefix(RESULT_PREFIX)?
+            .strip_prefix('\t')?
+            .split('\t');
+        let example = unescape(fields.next()?);
+        let part = fields.next()?.parse().ok()?;
+        let expected = unescape(fields.next()?);
+        let actual = fields.next()?;
+        let actual = (actual != NONE_MARKER).then(|| unescape(actual));
+
+        Some(Self {
+            example,
+            part,
+            expected,
+            actual,
+        })
+    }
+}
+
+/// Escapes line breaks and tabs, so that `s` fits into a single tab separated field.
+pub(crate) fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t")
+}
+
+pub(crate) fn unescape(s: &str) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            result.push(c);
+            continue;
+        }
+        match chars.next() {
+            Some('n') => result.push('\n'),
+            Some('t') => result.push('\t'),
+            Some(c) => result.push(c),
+            None => result.push('\\'),
+        }
+    }
+    result
+}
+
+/// Runs `func` against every example that declares an expected answer for `part`.
+pub fn run_examples<T: Display>(
+    examples: &[Example],
+    part: u8,
+    func: impl Fn(&str, &Context) -> Option<T>,
+) -> Vec<ExampleResult> {
+    examples
+        .iter()
+        .filter_map(|example| {
+            let expected = example.expected(part)?;
+            Some(ExampleResult {
+                example: example.name.clone(),
+                part,
+                expected: expected.into(),
+                actual: func(&example.input, &example.context).map(|x| x.to_string()),
+            })
+        })
+        .collect()
+}
+
+/// Panics with a list of all failed checks, if any. Warns if there was nothing to check.
+pub fn assert_examples(day: Day, results: &[ExampleResult]) {
+    if results.is_empty() {
+        // written to stderr directly, since the test harness only shows captured output of failed tests.
+        let _ = writeln!(
+            io::stderr(),
+            "warning: day {day} declares no expected answers in data/examples/{day}/, no example was checked."
+        );
+        return;
+    }
+
+    let failures: Vec<String> = results
+        .iter()
+        .filter(|result| !result.passed())
+        .map(|result| {
+            format!(
+                "  {} part {}: expected {}, got {}",
+                result.example,
+                result.part,
+                result.expected,
+                result.actual.as_deref().unwrap_or("None")
+            )
+        })
+        .collect();
+
+    assert!(
+        failures.is_empty(),
+        "day {day}: {} of {} example checks failed:\n{}",
+        failures.len(),
+        results.len(),
+        failures.join("\n")
+    );
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Example, ExampleResult, run_examples};
+
+    #[test]
+    fn serializes_results_to_lines() {
+        let result = ExampleResult {
+            example: "multi\tline".into(),
+            part: 2,
+            expected: "#.#\n.#.\\".into(),
+            actual: None,
+        };
+        let line = result.to_line();
+        assert!(!line.contains('\n'));
+        assert_eq!(ExampleResult::from_line(&line), Some(result));
+
+        let result = ExampleResult {
+            example: "example".into(),
+            part: 1,
+            expected: "3".into(),
+            actual: Some("✖".into()),
+        };
+        assert_eq!(ExampleResult::from_line(&result.to_line()), Some(result));
+        assert_eq!(ExampleResult::from_line("Part 1: 3"), None);
+    }
+
+    #[test]
+    fn parses_expected_answers() {
+        let example = Example::from_file_contents(
+            "larger",
+            "---\npart_1: 40\npart_2:\nconnections: 10\n---\n1,2,3\n",
+        );
+        assert_eq!(example.name, "larger");
+        assert_eq!(example.input, "1,2,3\n");
+        assert_eq!(example.expected(1), Some("40"));
+        assert_eq!(example.expected(2), None);
+        assert_eq!(example.expected(3), None);
+        assert_eq!(example.context.param::<u8>("connections"), Some(10));
+        assert_eq!(example.context.param::<String>("part_1"), None);
+    }
+
+    #[test]
+    fn runs_declared_parts_only() {
+        let examples = [
+            Example::from_file_contents("a", "---\npart_1: 3\n---\nabc"),
+            Example::from_file_contents("b", "---\npart_1: 5\npart_2: 1\n---\nabcd"),
+        ];
+
+        let results = run_examples(&examples, 1, |input, _| Some(input.len()));
+        assert_eq!(
+            results,
+            vec![
+                ExampleResult {
+                    example: "a".into(),
+                    part: 1,
+                    expected: "3".into(),
+                    actual: Some("3".into()),
+                },
+                ExampleResult {
+                    example: "b".into(),
+                    part: 1,
+                    expected: "5".into(),
+                    actual: Some("4".into()),
+                },
+            ]
+        );
+        assert!(results[0].passed());
+        assert!(!results[1].passed());
+
+        let results = run_examples(&examples, 2, |_, _| None::<u64>);
+        assert_eq!(results.len(), 1);
+        assert!(!results[0].passed());
+    }
+}
@@ -0,0 +1,332 @@
+/// Renders timings as standalone artifacts for `cargo time --export`.
+use std::{fmt::Display, str::FromStr, time::Duration};
+use tinyjson::JsonValue;
+
+use crate::template::timings::{PartTiming, Timing, Timings};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+    Markdown,
+    Svg,
+}
+
+#[derive(Debug)]
+pub struct UnknownFormatError(String);
+
+impl Display for UnknownFormatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "unknown export format `{}`, expected one of csv, json, md or svg",
+            self.0
+        )
+    }
+}
+
+impl std::error::Error for UnknownFormatError {}
+
+impl FromStr for ExportFormat {
+    type Err = UnknownFormatError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "csv" => Ok(ExportFormat::Csv),
+            "json" => Ok(ExportFormat::Json),
+            "md" | "markdown" => Ok(ExportFormat::Markdown),
+            "svg" => Ok(ExportFormat::Svg),
+            x => Err(UnknownFormatError(x.into())),
+        }
+    }
+}
+
+/// Renders `timings` in `format`, ordered by day.
+pub fn render(format: ExportFormat, timings: &Timings) -> String {
+    let mut data = timings.data.clone();
+    data.sort_unstable_by_key(|t| t.day);
+
+    match format {
+        ExportFormat::Csv => to_csv(&data),
+        ExportFormat::Json => JsonValue::from(Timings { data })
+            .format()
+            .expect("timings are valid JSON"),
+        ExportFormat::Markdown => to_markdown(&data),
+        ExportFormat::Svg => to_svg(&data),
+    }
+}
+
+fn parts(timing: &Timing) -> impl Iterator<Item = (u8, &PartTiming)> {
+    [(1, &timing.part_1), (2, &timing.part_2)]
+        .into_iter()
+        .filter_map(|(part, t)| t.as_ref().map(|t| (part, t)))
+}
+
+/// One row per part. Memory columns are empty for parts that were not profiled.
+fn to_csv(data: &[Timing]) -> String {
+    let mut csv = String::from(
+        "day,part,mean_nanos,median_nanos,min_nanos,max_nanos,std_dev_nanos,samples,peak_bytes,total_allocations,total_bytes\n",
+    );
+
+    for timing in data {
+        for (part, t) in parts(timing) {
+            let memory = t.memory.as_ref().map_or_else(
+                || ",,".into(),
+                |m| format!("{},{},{}", m.peak_bytes, m.total_allocations, m.total_bytes),
+            );
+            csv.push_str(&format!(
+                "{},{part},{},{},{},{},{},{},{memory}\n",
+                timing.day.into_inner(),
+                t.mean_nanos,
+                t.median_nanos,
+                t.min_nanos,
+                t.max_nanos,
+                t.std_dev_nanos,
+                t.samples,
+            ));
+        }
+    }
+
+    csv
+}
+
+fn format_nanos(nanos: f64) -> String {
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let duration = Duration::from_nanos(nanos as u64);
+    format!("{duration:.1?}")
+}
+
+fn to_markdown(data: &[Timing]) -> String {
+    let total_millis = data.iter().fold(0.0, |sum, t| sum + t.total_nanos) / 1_000_000_f64;
+
+    let mut lines: Vec<String> = vec![
+        "# Benchmarks".into(),
+        String::new(),
+        format!("**Total: {total_millis:.2}ms**"),
+        String::new(),
+        "| Day | Part | Mean | Median | Min | Max | Std. dev. | Samples | Memory |".into(),
+        "| :---: | :---: | ---: | ---: | ---: | ---: | ---: | ---: | ---: |".into(),
+    ];
+
+    for timing in data {
+        for (part, t) in parts(timing) {
+            lines.push(format!(
+                "| {} | {part} | `{}` | `{}` | `{}` | `{}` | `{}` | {} | {} |",
+                timing.day.into_inner(),
+                t.format(),
+                format_nanos(t.median_nanos),
+                format_nanos(t.min_nanos),
+                format_nanos(t.max_nanos),
+                format_nanos(t.std_dev_nanos),
+                t.samples,
+                t.memory
+                    .as_ref()
+                    .map_or_else(|| "-".into(), |m| format!("`{}`", m.format())),
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+/* -------------------------------------------------------------------------- */
+
+const SVG_LABEL_WIDTH: f64 = 70.0;
+const SVG_CHART_WIDTH: f64 = 600.0;
+const SVG_BAR_HEIGHT: f64 = 12.0;
+const SVG_DAY_GAP: f64 = 8.0;
+const SVG_HEADER_HEIGHT: f64 = 40.0;
+const SVG_AXIS_HEIGHT: f64 = 30.0;
+const SVG_PART_COLORS: [&str; 2] = ["#4e79a7", "#f28e2b"];
+
+/// Labels a power of ten in nanoseconds, e.g. `100µs` for `5`.
+fn format_decade(exponent: i32) -> String {
+    const UNITS: [&str; 4] = ["ns", "µs", "ms", "s"];
+    let unit = usize::try_from(exponent / 3)
+        .unwrap_or(0)
+        .min(UNITS.len() - 1);
+    #[allow(clippy::cast_possible_truncation)]
+    let value = 10_u64.pow((exponent - 3 * unit as i32) as u32);
+    format!("{value}{}", UNITS[unit])
+}
+
+/// Renders a horizontal bar per part, grouped by day, with times on a log scale.
+fn to_svg(data: &[Timing]) -> String {
+    let nanos: Vec<f64> = data
+        .iter()
+        .flat_map(parts)
+        .map(|(_, t)| t.mean_nanos.max(1.0))
+        .collect();
+
+    #[allow(clippy::cast_possible_truncation)]
+    let (min_exp, max_exp) = {
+        let min = nanos.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = nanos.iter().copied().fold(0.0, f64::max);
+        if nanos.is_empty() {
+            (0, 1)
+        } else {
+            let min_exp = min.log10().floor() as i32;
+            (min_exp, (max.log10().ceil() as i32).max(min_exp + 1))
+        }
+    };
+    let scale = |nanos: f64| {
+        (nanos.max(1.0).log10() - f64::from(min_exp)) / f64::from(max_exp - min_exp)
+            * SVG_CHART_WIDTH
+    };
+
+    let day_height = 2.0 * SVG_BAR_HEIGHT + SVG_DAY_GAP;
+    let chart_height = day_height * data.len() as f64;
+    let width = SVG_LABEL_WIDTH + SVG_CHART_WIDTH + 20.0;
+    let height = SVG_HEADER_HEIGHT + chart_height + SVG_AXIS_HEIGHT;
+
+    let mut svg: Vec<String> = vec![
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}" font-family="sans-serif" font-size="11">"#
+        ),
+        format!(r#"<rect width="{width}" height="{height}" fill="white"/>"#),
+        r#"<text x="10" y="18" font-size="14" font-weight="bold">Benchmarks</text>"#.into(),
+    ];
+
+    for (index, color) in SVG_PART_COLORS.iter().enumerate() {
+        let x = SVG_LABEL_WIDTH + 100.0 * index as f64;
+        svg.push(format!(
+            r#"<rect x="{x}" y="26" width="10" height="10" fill="{color}"/><text x="{}" y="35">Part {}</text>"#,
+            x + 14.0,
+            index + 1
+        ));
+    }
+
+    // grid lines at every power of ten.
+    for exponent in min_exp..=max_exp {
+        let x = SVG_LABEL_WIDTH + scale(10_f64.powi(exponent));
+        svg.push(format!(
+            r##"<line x1="{x:.1}" y1="{SVG_HEADER_HEIGHT}" x2="{x:.1}" y2="{:.1}" stroke="#ddd"/>"##,
+            SVG_HEADER_HEIGHT + chart_height
+        ));
+        svg.push(format!(
+            r#"<text x="{x:.1}" y="{:.1}" text-anchor="middle">{}</text>"#,
+            SVG_HEADER_HEIGHT + chart_height + 16.0,
+            format_decade(exponent)
+        ));
+    }
+
+    for (index, timing) in data.iter().enumerate() {
+        let y = SVG_HEADER_HEIGHT + day_height * index as f64;
+        svg.push(format!(
+            r#"<text x="10" y="{:.1}">Day {}</text>"#,
+            y + SVG_BAR_HEIGHT + 4.0,
+            timing.day.into_inner()
+        ));
+
+        for (part, t) in parts(timing) {
+            let bar_y = y + SVG_BAR_HEIGHT * f64::from(part - 1);
+            svg.push(format!(
+                r#"<rect x="{SVG_LABEL_WIDTH}" y="{bar_y:.1}" width="{:.1}" height="{SVG_BAR_HEIGHT}" fill="{}"><title>Day {} Part {part}: {}</title></rect>"#,
+                scale(t.mean_nanos).max(1.0),
+                SVG_PART_COLORS[usize::from(part - 1)],
+                timing.day.into_inner(),
+                t.format()
+            ));
+        }
+    }
+
+    svg.push("</svg>".into());
+    svg.push(String::new());
+    svg.join("\n")
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::day;
+    use crate::template::timings::{MemoryStats, PartTiming, Timing, Timings};
+
+    use super::{ExportFormat, format_decade, render};
+
+    fn get_mock_timings() -> Timings {
+        let mut part_2 = PartTiming::from_mean(2e6, 10);
+        part_2.memory = Some(MemoryStats {
+            peak_bytes: 2048,
+            total_allocations: 3,
+            total_bytes: 4096,
+        });
+
+        Timings {
+            data: vec![
+                Timing {
+                    day: day!(4),
+                    part_1: Some(PartTiming::from_mean(50.0, 10000)),
+                    part_2: None,
+                    total_nanos: 50.0,
+                    failure: None,
+                },
+                Timing {
+                    day: day!(1),
+                    part_1: Some(PartTiming::from_mean(1500.0, 100)),
+                    part_2: Some(part_2),
+                    total_nanos: 2_001_500.0,
+                    failure: None,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!(
+            "md".parse::<ExportFormat>().unwrap(),
+            ExportFormat::Markdown
+        );
+        assert_eq!("svg".parse::<ExportFormat>().unwrap(), ExportFormat::Svg);
+        assert!("png".parse::<ExportFormat>().is_err());
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = render(ExportFormat::Csv, &get_mock_timings());
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(lines[1], "1,1,1500,1500,1500,1500,0,100,,,");
+        assert_eq!(
+            lines[2],
+            "1,2,2000000,2000000,2000000,2000000,0,10,2048,3,4096"
+        );
+        assert_eq!(lines[3], "4,1,50,50,50,50,0,10000,,,");
+    }
+
+    #[test]
+    fn exports_json() {
+        let json = render(ExportFormat::Json, &get_mock_timings());
+        let timings = Timings::try_from(json).unwrap();
+        assert_eq!(timings.data[0].day, day!(1));
+        assert_eq!(timings.data[0].part_2, get_mock_timings().data[1].part_2);
+    }
+
+    #[test]
+    fn exports_markdown() {
+        let md = render(ExportFormat::Markdown, &get_mock_timings());
+        assert!(md.starts_with("# Benchmarks\n\n**Total: 2.00ms**"));
+        assert!(md.contains(
+            "| 1 | 2 | `2.0ms` | `2.0ms` | `2.0ms` | `2.0ms` | `0.0ns` | 10 | `2.0 KiB (3 allocs)` |"
+        ));
+        assert!(md.contains("| 4 | 1 | `50.0ns` |"));
+    }
+
+    #[test]
+    fn exports_svg() {
+        let svg = render(ExportFormat::Svg, &get_mock_timings());
+        assert!(svg.starts_with("<svg "));
+        assert!(svg.trim_end().ends_with("</svg>"));
+        assert_eq!(svg.matches("<title>").count(), 3);
+        // 50ns to 2ms spans the decades from 10ns to 10ms.
+        assert!(svg.contains(">10ns</text>"));
+        assert!(svg.contains(">10ms</text>"));
+    }
+
+    #[test]
+    fn formats_decades() {
+        assert_eq!(format_decade(0), "1ns");
+        assert_eq!(format_decade(5), "100µs");
+        assert_eq!(format_decade(10), "10s");
+    }
+}
@@ -0,0 +1,526 @@
+/// Append-only history of benchmark runs, used to detect regressions across commits.
+///
+/// Every stored `cargo time` run appends one JSON object per day to the history file, so that
+/// older results are never overwritten.
+use std::{
+    collections::HashMap,
+    env, fs,
+    io::{Error, Write},
+    process::Command,
+    str::FromStr,
+    thread,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::Day;
+use crate::template::timings::Timings;
+
+static HISTORY_FILE_PATH: &str = "./data/timings_history.jsonl";
+
+/// Describes where and when a benchmark run happened.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunInfo {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Short hash of the checked out commit, suffixed with `-dirty` for uncommitted changes.
+    pub commit: Option<String>,
+    pub profile: String,
+    pub machine: String,
+    /// Optional name that can be used as a baseline for later comparisons.
+    pub tag: Option<String>,
+}
+
+impl RunInfo {
+    /// Collects information about the current environment.
+    pub fn current(profile: &str, tag: Option<String>) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |d| d.as_secs()),
+            commit: git_commit(),
+            profile: profile.into(),
+            machine: machine_fingerprint(),
+            tag,
+        }
+    }
+}
+
+/// A single day of a benchmark run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct HistoryEntry {
+    pub run: RunInfo,
+    pub day: Day,
+    /// Mean time of each part. `None` for entries written before part timings were recorded.
+    pub part_1_nanos: Option<f64>,
+    pub part_2_nanos: Option<f64>,
+    pub total_nanos: f64,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub entries: Vec<HistoryEntry>,
+}
+
+impl History {
+    /// Rehydrate the history from its file. If not present, returns an empty history.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(HISTORY_FILE_PATH)
+            .map(|contents| {
+                let (history, skipped) = History::parse(&contents);
+                for (line, err) in skipped {
+                    eprintln!("warning: skipped line {line} of {HISTORY_FILE_PATH}: {err}");
+                }
+                history
+            })
+            .unwrap_or_default()
+    }
+
+    /// Parses the history file. Empty lines are skipped, so are malformed lines, which are returned
+    /// with their line number and the reason they were skipped.
+    pub fn parse(s: &str) -> (Self, Vec<(usize, String)>) {
+        let mut entries = vec![];
+        let mut skipped = vec![];
+
+        for (line, number) in s.lines().zip(1..) {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = JsonValue::from_str(line)
+                .or(Err("not a valid JSON line.".to_string()))
+                .and_then(|json| HistoryEntry::try_from(&json));
+            match entry {
+                Ok(entry) => entries.push(entry),
+                Err(err) => skipped.push((number, err)),
+            }
+        }
+
+        (History { entries }, skipped)
+    }
+
+    /// Append the timings of a run to the history file.
+    pub fn append_to_file(run: &RunInfo, timings: &Timings) -> Result<(), Error> {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(HISTORY_FILE_PATH)?;
+
+        for entry in Self::entries_for(run, timings) {
+            let line = JsonValue::from(&entry)
+                .stringify()
+                .map_err(|e| Error::other(e.to_string()))?;
+            writeln!(file, "{line}")?;
+        }
+        Ok(())
+    }
+
+    pub fn entries_for(run: &RunInfo, timings: &Timings) -> Vec<HistoryEntry> {
+        timings
+            .data
+            .iter()
+            .filter(|timing| timing.total_nanos > 0.0)
+            .map(|timing| HistoryEntry {
+                run: run.clone(),
+                day: timing.day,
+                part_1_nanos: timing.part_1.as_ref().map(|p| p.mean_nanos),
+                part_2_nanos: timing.part_2.as_ref().map(|p| p.mean_nanos),
+                total_nanos: timing.total_nanos,
+            })
+            .collect()
+    }
+
+    /// The most recent entry for `day` that was recorded on the same machine with the same profile as `run`.
+    /// If `baseline` is set, only entries whose tag equals it or whose commit starts with it are considered.
+    pub fn baseline_for(
+        &self,
+        day: Day,
+        run: &RunInfo,
+        baseline: Option<&str>,
+    ) -> Option<&HistoryEntry> {
+        self.entries
+            .iter()
+            .rev()
+            .filter(|e| {
+                e.day == day && e.run.machine == run.machine && e.run.profile == run.profile
+            })
+            .find(|e| {
+                baseline.is_none_or(|name| {
+                    e.run.tag.as_deref() == Some(name)
+                        || e.run.commit.as_ref().is_some_and(|c| c.starts_with(name))
+                })
+            })
+    }
+
+    /// Renders the history as CSV, one row per entry.
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "timestamp,commit,profile,machine,tag,day,part_1_nanos,part_2_nanos,total_nanos\n",
+        );
+        for entry in &self.entries {
+            let fields = [
+                format_timestamp(entry.run.timestamp),
+                entry.run.commit.clone().unwrap_or_default(),
+                entry.run.profile.clone(),
+                entry.run.machine.clone(),
+                entry.run.tag.clone().unwrap_or_default(),
+                entry.day.to_string(),
+                entry
+                    .part_1_nanos
+                    .map(|n| n.to_string())
+                    .unwrap_or_default(),
+                entry
+                    .part_2_nanos
+                    .map(|n| n.to_string())
+                    .unwrap_or_default(),
+                entry.total_nanos.to_string(),
+            ];
+            let row: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+            csv += &row.join(",");
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+/// The change of a day's total time compared to a baseline.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Comparison {
+    pub day: Day,
+    pub baseline: HistoryEntry,
+    pub total_nanos: f64,
+}
+
+impl Comparison {
+    /// Relative change in percent, positive values mean slower.
+    pub fn change_percent(&self) -> f64 {
+        (self.total_nanos / self.baseline.total_nanos - 1.0) * 100.0
+    }
+
+    pub fn is_regression(&self, threshold_percent: f64) -> bool {
+        self.change_percent() > threshold_percent
+    }
+}
+
+/// Compares `timings` to the baseline of each day. Days without a baseline are skipped.
+pub fn compare(
+    history: &History,
+    run: &RunInfo,
+    timings: &Timings,
+    baseline: Option<&str>,
+) -> Vec<Comparison> {
+    timings
+        .data
+        .iter()
+        .filter(|timing| timing.total_nanos > 0.0)
+        .filter_map(|timing| {
+            let baseline = history.baseline_for(timing.day, run, baseline)?;
+            Some(Comparison {
+                day: timing.day,
+                baseline: baseline.clone(),
+                total_nanos: timing.total_nanos,
+            })
+        })
+        .collect()
+}
+
+fn git_commit() -> Option<String> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+
+    let hash = git(&["rev-parse", "--short", "HEAD"])?;
+    let is_dirty = git(&["status", "--porcelain", "--untracked-files=no"])
+        .is_some_and(|status| !status.is_empty());
+
+    Some(if is_dirty {
+        format!("{hash}-dirty")
+    } else {
+        hash
+    })
+}
+
+/// A stable identifier of the current machine, derived from its hostname, platform and CPU.
+pub fn machine_fingerprint() -> String {
+    let hostname = env::var("HOSTNAME")
+        .or_else(|_| env::var("COMPUTERNAME"))
+        .or_else(|_| fs::read_to_string("/etc/hostname"))
+        .unwrap_or_default();
+
+    let cpu = fs::read_to_string("/proc/cpuinfo")
+        .ok()
+        .and_then(|info| {
+            info.lines()
+                .find(|line| line.starts_with("model name"))
+                .map(str::to_string)
+        })
+        .unwrap_or_default();
+
+    let cpus = thread::available_parallelism().map_or(0, |n| n.get());
+
+    let hash = fnv1a(
+        format!(
+            "{}|{}|{}|{cpu}|{cpus}",
+            hostname.trim(),
+            env::consts::OS,
+            env::consts::ARCH
+        )
+        .as_bytes(),
+    );
+    format!("{}-{}-{hash:016x}", env::consts::OS, env::consts::ARCH)
+}
+
+/// FNV-1a, used instead of the std hasher whose output may change between Rust versions.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Formats seconds since the unix epoch as an ISO 8601 UTC timestamp.
+pub fn format_timestamp(timestamp: u64) -> String {
+    let days = (timestamp / 86_400) as i64;
+    let seconds = timestamp % 86_400;
+
+    // see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        seconds / 3600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.into()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn optional_string(value: Option<&String>) -> JsonValue {
+    value.map_or(JsonValue::Null, |v| JsonValue::String(v.clone()))
+}
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.run.timestamp as f64),
+        );
+        map.insert("commit".into(), optional_string(value.run.commit.as_ref()));
+        map.insert(
+            "profile".into(),
+            JsonValue::String(value.run.profile.clone()),
+        );
+        map.insert(
+            "machine".into(),
+            JsonValue::String(value.run.machine.clone()),
+        );
+        map.insert("tag".into(), optional_string(value.run.tag.as_ref()));
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert(
+            "part_1_nanos".into(),
+            value
+                .part_1_nanos
+                .map_or(JsonValue::Null, JsonValue::Number),
+        );
+        map.insert(
+            "part_2_nanos".into(),
+            value
+                .part_2_nanos
+                .map_or(JsonValue::Null, JsonValue::Number),
+        );
+        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for HistoryEntry {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let string = |key: &str| json.get(key).and_then(|v| v.get::<String>()).cloned();
+        let number = |key: &str| json.get(key).and_then(|v| v.get::<f64>()).copied();
+
+        let day = string("day")
+            .and_then(|day| Day::from_str(&day).ok())
+            .ok_or("Expected entry.day to be a Day struct.")?;
+
+        Ok(HistoryEntry {
+            run: RunInfo {
+                timestamp: number("timestamp").ok_or("Expected entry.timestamp to be a number.")?
+                    as u64,
+                commit: string("commit"),
+                profile: string("profile").ok_or("Expected entry.profile to be a string.")?,
+                machine: string("machine").ok_or("Expected entry.machine to be a string.")?,
+                tag: string("tag"),
+            },
+            day,
+            part_1_nanos: number("part_1_nanos"),
+            part_2_nanos: number("part_2_nanos"),
+            total_nanos: number("total_nanos")
+                .ok_or("Expected entry.total_nanos to be a number.")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::{
+        day,
+        template::timings::{PartTiming, Timing, Timings},
+    };
+
+    use super::{History, RunInfo, compare, format_timestamp};
+
+    fn run(timestamp: u64, commit: &str, tag: Option<&str>) -> RunInfo {
+        RunInfo {
+            timestamp,
+            commit: Some(commit.into()),
+            profile: "release".into(),
+            machine: "linux-x86_64-0000000000000001".into(),
+            tag: tag.map(Into::into),
+        }
+    }
+
+    fn timings(total_nanos: f64) -> Timings {
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1: Some(PartTiming::from_mean(total_nanos, 10)),
+                part_2: None,
+                total_nanos,
+                failure: None,
+            }],
+        }
+    }
+
+    fn history() -> History {
+        let mut entries = History::entries_for(&run(1, "aaaaaaa", Some("v1")), &timings(100.0));
+        entries.extend(History::entries_for(
+            &run(2, "bbbbbbb", None),
+            &timings(200.0),
+        ));
+        History { entries }
+    }
+
+    #[test]
+    fn roundtrips_json_lines() {
+        let history = history();
+        let lines: Vec<String> = history
+            .entries
+            .iter()
+            .map(|e| tinyjson::JsonValue::from(e).stringify().unwrap())
+            .collect();
+        let (parsed, skipped) = History::parse(&(lines.join("\n") + "\n\n"));
+        assert_eq!(parsed.entries, history.entries);
+        assert!(skipped.is_empty());
+    }
+
+    #[test]
+    fn reads_entries_without_part_timings() {
+        let line = r#"{ "timestamp": 1, "commit": null, "profile": "release", "machine": "m", "tag": null, "day": "03", "total_nanos": 5 }"#;
+        let (parsed, skipped) = History::parse(line);
+        assert!(skipped.is_empty());
+        let entry = &parsed.entries[0];
+        assert_eq!(entry.part_1_nanos, None);
+        assert_eq!(entry.total_nanos, 5.0);
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let history = history();
+        let mut lines: Vec<String> = history
+            .entries
+            .iter()
+            .map(|e| tinyjson::JsonValue::from(e).stringify().unwrap())
+            .collect();
+        lines.insert(1, r#"{ "day": "01" }"#.into());
+        lines.insert(2, "{ \"timestamp\": 3, ".into());
+
+        let (parsed, skipped) = History::parse(&lines.join("\n"));
+        assert_eq!(parsed.entries, history.entries);
+        let skipped_lines: Vec<usize> = skipped.iter().map(|(line, _)| *line).collect();
+        assert_eq!(skipped_lines, [2, 3]);
+        assert_eq!(skipped[0].1, "Expected entry.timestamp to be a number.");
+        assert_eq!(skipped[1].1, "not a valid JSON line.");
+    }
+
+    #[test]
+    fn selects_baselines() {
+        let history = history();
+        let current = run(3, "ccccccc", None);
+
+        let latest = history.baseline_for(day!(1), &current, None).unwrap();
+        assert_eq!(latest.total_nanos, 200.0);
+        let tagged = history.baseline_for(day!(1), &current, Some("v1")).unwrap();
+        assert_eq!(tagged.total_nanos, 100.0);
+        let by_commit = history
+            .baseline_for(day!(1), &current, Some("aaa"))
+            .unwrap();
+        assert_eq!(by_commit.total_nanos, 100.0);
+        assert_eq!(history.baseline_for(day!(2), &current, None), None);
+
+        let other_machine = RunInfo {
+            machine: "other".into(),
+            ..current
+        };
+        assert_eq!(history.baseline_for(day!(1), &other_machine, None), None);
+    }
+
+    #[test]
+    fn flags_regressions() {
+        let history = history();
+        let comparisons = compare(&history, &run(3, "c", None), &timings(230.0), None);
+        assert_eq!(comparisons.len(), 1);
+        assert!((comparisons[0].change_percent() - 15.0).abs() < 1e-9);
+        assert!(comparisons[0].is_regression(10.0));
+        assert!(!comparisons[0].is_regression(20.0));
+    }
+
+    #[test]
+    fn exports_csv() {
+        let mut history = history();
+        history.entries.truncate(1);
+        history.entries[0].run.tag = Some("a,\"b\"".into());
+        assert_eq!(
+            history.to_csv(),
+            "timestamp,commit,profile,machine,tag,day,part_1_nanos,part_2_nanos,total_nanos\n\
+            1970-01-01T00:00:01Z,aaaaaaa,release,linux-x86_64-0000000000000001,\"a,\"\"b\"\"\",01,100,,100\n"
+        );
+    }
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01T00:00:00Z");
+        assert_eq!(format_timestamp(951_782_400), "2000-02-29T00:00:00Z");
+        assert_eq!(format_timestamp(1_764_547_199), "2025-11-30T23:59:59Z");
+    }
+}
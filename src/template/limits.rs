@@ -0,0 +1,190 @@
+/// Timeouts and memory limits for the solution runs of `cargo all` and `cargo time`.
+use std::{
+    fs, io,
+    process::{Child, ExitStatus},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::template::timings::{Failure, PartFailure, format_bytes};
+
+/// How often a supervised run is checked against its limits.
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Limits {
+    /// Maximum run time of a single day.
+    pub timeout: Option<Duration>,
+    /// Maximum run time of all days together. Days that did not start in time are skipped.
+    pub total_timeout: Option<Duration>,
+    /// Maximum resident memory of a single day, in bytes.
+    pub memory_bytes: Option<u64>,
+}
+
+impl Limits {
+    /// The point in time after which no further day is started.
+    pub fn deadline(&self) -> Option<Instant> {
+        self.total_timeout.map(|timeout| Instant::now() + timeout)
+    }
+
+    /// The timeout of a day that starts now: the per-day timeout, capped by the time left until `deadline`.
+    pub fn day_timeout(&self, deadline: Option<Instant>) -> Option<Duration> {
+        let remaining = deadline.map(|deadline| deadline.saturating_duration_since(Instant::now()));
+        match (self.timeout, remaining) {
+            (Some(timeout), Some(remaining)) => Some(timeout.min(remaining)),
+            (timeout, remaining) => timeout.or(remaining),
+        }
+    }
+
+    /// Warns about limits that cannot be enforced on this platform.
+    pub fn warn_if_unsupported(&self) {
+        if self.memory_bytes.is_some() && !cfg!(target_os = "linux") {
+            eprintln!("Memory limits are only supported on Linux, --memory-limit is ignored.");
+        }
+    }
+
+    /// Describes why a part was stopped, e.g. `Part 2: ✖ timed out after 1.0s`.
+    pub fn describe(&self, failure: PartFailure, timeout: Option<Duration>) -> String {
+        let detail = match failure.failure {
+            Failure::Timeout => timeout.map(|t| format!(" after {t:.1?}")),
+            Failure::MemoryLimit => self
+                .memory_bytes
+                .map(|bytes| format!(", the limit is {}", format_bytes(bytes))),
+        };
+        format!(
+            "Part {}: ✖ {}{}",
+            failure.part,
+            failure.failure,
+            detail.unwrap_or_default()
+        )
+    }
+}
+
+pub fn is_past(deadline: Option<Instant>) -> bool {
+    deadline.is_some_and(|deadline| Instant::now() >= deadline)
+}
+
+/// How a supervised run ended.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Exited(ExitStatus),
+    /// The run was killed for exceeding a limit.
+    Stopped(Failure),
+}
+
+/// Waits for `child` to exit. Kills it once it runs longer than `timeout`
+/// or its resident memory exceeds `memory_bytes`.
+pub fn supervise(
+    child: &mut Child,
+    timeout: Option<Duration>,
+    memory_bytes: Option<u64>,
+) -> io::Result<Outcome> {
+    if timeout.is_none() && memory_bytes.is_none() {
+        return child.wait().map(Outcome::Exited);
+    }
+
+    let start = Instant::now();
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Outcome::Exited(status));
+        }
+
+        let failure = if timeout.is_some_and(|timeout| start.elapsed() >= timeout) {
+            Some(Failure::Timeout)
+        } else if memory_bytes
+            .is_some_and(|limit| resident_bytes(child.id()).is_some_and(|bytes| bytes > limit))
+        {
+            Some(Failure::MemoryLimit)
+        } else {
+            None
+        };
+
+        if let Some(failure) = failure {
+            // the child may have exited since `try_wait`, in which case there is nothing left to kill.
+            let _ = child.kill();
+            child.wait()?;
+            return Ok(Outcome::Stopped(failure));
+        }
+
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+/// Reads the resident memory of a process from `/proc`. Returns `None` on other platforms than Linux.
+fn resident_bytes(pid: u32) -> Option<u64> {
+    let status = fs::read_to_string(format!("/proc/{pid}/status")).ok()?;
+    let kib = status
+        .lines()
+        .find_map(|line| line.strip_prefix("VmRSS:"))?
+        .trim()
+        .strip_suffix("kB")?
+        .trim()
+        .parse::<u64>()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        process::Command,
+        time::{Duration, Instant},
+    };
+
+    use super::{Limits, Outcome, supervise};
+    use crate::template::timings::{Failure, PartFailure};
+
+    #[test]
+    fn caps_day_timeout_by_deadline() {
+        let limits = Limits {
+            timeout: Some(Duration::from_secs(10)),
+            ..Limits::default()
+        };
+        assert_eq!(limits.day_timeout(None), Some(Duration::from_secs(10)));
+
+        let deadline = Instant::now() + Duration::from_secs(2);
+        assert!(limits.day_timeout(Some(deadline)) <= Some(Duration::from_secs(2)));
+        assert_eq!(Limits::default().day_timeout(None), None);
+    }
+
+    #[test]
+    fn describes_failures() {
+        let limits = Limits {
+            memory_bytes: Some(512 * 1024 * 1024),
+            ..Limits::default()
+        };
+        let failure = |failure| PartFailure { part: 2, failure };
+        assert_eq!(
+            limits.describe(failure(Failure::Timeout), Some(Duration::from_secs(1))),
+            "Part 2: ✖ timed out after 1.0s"
+        );
+        assert_eq!(
+            limits.describe(failure(Failure::MemoryLimit), None),
+            "Part 2: ✖ out of memory, the limit is 512.0 MiB"
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn kills_after_timeout() {
+        let mut child = Command::new("sleep").arg("5").spawn().unwrap();
+        let start = Instant::now();
+        let failure = supervise(&mut child, Some(Duration::from_millis(50)), None).unwrap();
+        assert_eq!(failure, Outcome::Stopped(Failure::Timeout));
+        assert!(start.elapsed() < Duration::from_secs(5));
+
+        let mut child = Command::new("true").spawn().unwrap();
+        let outcome = supervise(&mut child, Some(Duration::from_secs(5)), None).unwrap();
+        assert!(matches!(outcome, Outcome::Exited(status) if status.success()));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn kills_above_memory_limit() {
+        assert!(super::resident_bytes(std::process::id()).is_some_and(|bytes| bytes > 0));
+
+        let mut child = Command::new("sleep").arg("5").spawn().unwrap();
+        let failure = supervise(&mut child, None, Some(1)).unwrap();
+        assert_eq!(failure, Outcome::Stopped(Failure::MemoryLimit));
+    }
+}
@@ -0,0 +1,250 @@
+/// Renders the markdown of a puzzle description for the terminal, with ANSI formatting for headings,
+/// emphasis and code.
+use crate::template::puzzle::{heading, is_fence};
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+const ANSI_CODE: &str = "\x1b[36m";
+const ANSI_DIM: &str = "\x1b[2m";
+
+/// Renders `markdown`, wrapping paragraphs at `width` columns. Part two is separated from part one by a rule.
+/// Without `is_styled`, the text contains no ANSI escape sequences, e.g. when it is piped to a file.
+pub fn render(markdown: &str, width: usize, is_styled: bool) -> String {
+    let mut lines: Vec<String> = vec![];
+    let mut source = markdown.lines().peekable();
+    let mut in_code_block = false;
+
+    while let Some(line) = source.next() {
+        if is_fence(line) {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            lines.push(format!("    {ANSI_CODE}{line}{ANSI_RESET}"));
+            continue;
+        }
+
+        if let Some(text) = heading(line) {
+            if source.peek().is_some_and(|next| is_underline(next)) {
+                source.next();
+            }
+            if text.starts_with("Part ") && !lines.is_empty() {
+                push_blank(&mut lines);
+                lines.push(format!("{ANSI_DIM}{}{ANSI_RESET}", "─".repeat(width)));
+                push_blank(&mut lines);
+            }
+            lines.push(format!("{ANSI_BOLD}--- {text} ---{ANSI_RESET}"));
+            continue;
+        }
+
+        let line = line.trim();
+        if line.is_empty() {
+            push_blank(&mut lines);
+        } else if let Some(item) = line.strip_prefix("- ").or_else(|| line.strip_prefix("* ")) {
+            lines.extend(wrap(&render_inline(item), width, "  • ", "    "));
+        } else {
+            lines.extend(wrap(&render_inline(line), width, "", ""));
+        }
+    }
+
+    while lines.last().is_some_and(String::is_empty) {
+        lines.pop();
+    }
+    lines.push(String::new());
+    let text = lines.join("\n");
+    if is_styled { text } else { strip_styles(&text) }
+}
+
+/// Removes ANSI escape sequences from `text`.
+fn strip_styles(text: &str) -> String {
+    let mut plain = String::with_capacity(text.len());
+    let mut in_escape = false;
+    for c in text.chars() {
+        match (in_escape, c) {
+            (false, '\x1b') => in_escape = true,
+            (false, _) => plain.push(c),
+            (true, 'm') => in_escape = false,
+            (true, _) => {}
+        }
+    }
+    plain
+}
+
+/// Whether `line` underlines the heading above it, as in `----------`.
+fn is_underline(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && line.chars().all(|c| c == '-' || c == '=')
+}
+
+fn push_blank(lines: &mut Vec<String>) {
+    if lines.last().is_some_and(|line| !line.is_empty()) {
+        lines.push(String::new());
+    }
+}
+
+fn style(emphasis: bool, code: bool) -> String {
+    format!(
+        "{ANSI_RESET}{}{}",
+        if emphasis { ANSI_BOLD } else { "" },
+        if code { ANSI_CODE } else { "" }
+    )
+}
+
+/// Renders emphasis, inline code, links and escaped characters of a single line.
+fn render_inline(text: &str) -> String {
+    let mut out = String::new();
+    let mut emphasis = false;
+    let mut rest = text;
+
+    while let Some(c) = rest.chars().next() {
+        let next = &rest[c.len_utf8()..];
+        match c {
+            '\\' => match next.chars().next() {
+                Some(escaped) if escaped.is_ascii_punctuation() => {
+                    out.push(escaped);
+                    rest = &next[1..];
+                }
+                _ => {
+                    out.push(c);
+                    rest = next;
+                }
+            },
+            '`' if next.contains('`') => {
+                let (code, after) = next.split_once('`').unwrap();
+                // emphasized code is written as `*42*`.
+                let (code, emphasized) =
+                    match code.strip_prefix('*').and_then(|c| c.strip_suffix('*')) {
+                        Some(inner) if !inner.is_empty() => (inner, true),
+                        _ => (code, false),
+                    };
+                out.push_str(&style(emphasis || emphasized, true));
+                out.push_str(code);
+                out.push_str(&style(emphasis, false));
+                rest = after;
+            }
+            '*' => {
+                emphasis = !emphasis;
+                out.push_str(&style(emphasis, false));
+                rest = next.strip_prefix('*').unwrap_or(next);
+            }
+            '[' => match parse_link(next) {
+                Some((label, after)) => {
+                    out.push_str(&render_inline(label));
+                    out.push_str(&style(emphasis, false));
+                    rest = after;
+                }
+                None => {
+                    out.push(c);
+                    rest = next;
+                }
+            },
+            _ => {
+                out.push(c);
+                rest = next;
+            }
+        }
+    }
+
+    if emphasis {
+        out.push_str(ANSI_RESET);
+    }
+    out
+}
+
+/// Splits `label](url) rest` into the label and the rest.
+fn parse_link(text: &str) -> Option<(&str, &str)> {
+    let (label, after) = text.split_once("](")?;
+    let (_, rest) = after.split_once(')')?;
+    Some((label, rest))
+}
+
+/// The width of `text` in the terminal, without ANSI escape sequences.
+fn visible_len(text: &str) -> usize {
+    strip_styles(text).chars().count()
+}
+
+/// Wraps `text` at `width` columns. The first line starts with `first_indent`, all others with `indent`.
+fn wrap(text: &str, width: usize, first_indent: &str, indent: &str) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = first_indent.to_string();
+    let mut line_len = visible_len(first_indent);
+    let mut is_first_word = true;
+
+    for word in text.split(' ') {
+        let word_len = visible_len(word);
+        if !is_first_word && line_len + 1 + word_len > width {
+            lines.push(line);
+            line = indent.to_string();
+            line_len = visible_len(indent);
+            is_first_word = true;
+        }
+        if !is_first_word {
+            line.push(' ');
+            line_len += 1;
+        }
+        line.push_str(word);
+        line_len += word_len;
+        is_first_word = false;
+    }
+
+    lines.push(line);
+    lines
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{ANSI_CODE, render, render_inline, visible_len, wrap};
+    use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+    const MARKDOWN: &str = include_str!("fixtures/puzzle.md");
+
+    #[test]
+    fn renders_inline_formatting() {
+        assert_eq!(
+            render_inline("a *b* c"),
+            format!("a {ANSI_RESET}{ANSI_BOLD}b{ANSI_RESET} c")
+        );
+        assert_eq!(
+            render_inline("is `*3*`."),
+            format!("is {ANSI_RESET}{ANSI_BOLD}{ANSI_CODE}3{ANSI_RESET}.")
+        );
+        assert_eq!(render_inline(r"stars: \*\*"), "stars: **");
+        assert_eq!(
+            render_inline("see [the site](https://adventofcode.com)!"),
+            format!("see the site{ANSI_RESET}!")
+        );
+    }
+
+    #[test]
+    fn wraps_by_visible_width() {
+        let text = render_inline("one *two* three four");
+        let lines = wrap(&text, 9, "", "  ");
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().all(|line| visible_len(line) <= 9));
+        assert!(lines[1].starts_with("  "));
+    }
+
+    #[test]
+    fn renders_puzzle() {
+        let rendered = render(MARKDOWN, 60, true);
+        assert!(rendered.starts_with(&format!(
+            "{ANSI_BOLD}--- Day 1: Sonar Snowdrift ---{ANSI_RESET}\n\n"
+        )));
+        assert!(rendered.contains(&format!("\n    {ANSI_CODE}199{ANSI_RESET}\n")));
+        assert!(rendered.contains(&format!(
+            "─{ANSI_RESET}\n\n{ANSI_BOLD}--- Part Two ---{ANSI_RESET}\n"
+        )));
+        assert!(!rendered.contains("----------"));
+        assert!(!rendered.contains("```"));
+        assert!(rendered.ends_with("gold stars: **\n"));
+        assert!(rendered.lines().all(|line| visible_len(line) <= 60));
+    }
+
+    #[test]
+    fn renders_plain_text() {
+        let rendered = render(MARKDOWN, 60, false);
+        assert!(!rendered.contains('\x1b'));
+        assert!(rendered.starts_with("--- Day 1: Sonar Snowdrift ---\n\n"));
+        assert!(rendered.contains("\n    199\n"));
+        assert!(rendered.lines().all(|line| line.chars().count() <= 60));
+    }
+}
@@ -1,57 +1,119 @@
-use std::{env, fs};
+use std::{fs, io, path::PathBuf};
+
+use crate::template::cli::InputSource;
 
 pub mod aoc_cli;
+pub mod cli;
 pub mod commands;
+pub mod examples;
+pub mod export;
+pub mod limits;
+pub mod puzzle;
+pub mod report;
 pub mod runner;
+pub mod templates;
+pub mod variants;
 
+pub use context::*;
 pub use day::*;
 
+mod answers;
+mod context;
 mod day;
+mod history;
+mod markdown;
 mod readme_benchmarks;
+mod readme_stars;
 mod run_multi;
 mod timings;
+mod watch;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
-#[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
-}
+/// Reads the input of `day` from `source`, or from `data/inputs/NN.txt` if no source is given,
+/// along with the [`Context`] declared in its front matter.
+///
+/// # Errors
+/// Returns a message for the user if the input cannot be read.
+pub fn read_input(day: Day, source: Option<&InputSource>) -> Result<(String, Context), String> {
+    let read = |path: PathBuf| {
+        fs::read_to_string(&path)
+            .map_err(|err| format!("Could not read input file \"{}\": {err}.", path.display()))
+    };
 
-/// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
-#[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    let contents = match source {
+        None => {
+            let path = PathBuf::from("data")
+                .join("inputs")
+                .join(format!("{day}.txt"));
+            read(path).map_err(|err| {
+                format!("{err}\nRun `cargo download {day}`, or pass `--input <path>` or `--example [name]`.")
+            })?
+        }
+        Some(InputSource::File(path)) => read(path.clone())?,
+        Some(InputSource::Stdin) => io::read_to_string(io::stdin())
+            .map_err(|err| format!("Could not read input from stdin: {err}."))?,
+        Some(InputSource::Example(name)) => {
+            let examples = examples::read_examples(day);
+            let example = match name {
+                Some(name) => examples.iter().find(|example| &example.name == name),
+                None => examples.first(),
+            };
+            return match example {
+                Some(example) => Ok((example.input.clone(), example.context.clone())),
+                None if examples.is_empty() => Err(format!("Day {day} has no examples.")),
+                None => Err(format!(
+                    "Day {day} has no example named \"{}\". Available examples: {}.",
+                    name.as_deref().unwrap_or_default(),
+                    examples
+                        .iter()
+                        .map(|example| example.name.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                )),
+            };
+        }
+    };
+
+    let (context, input) = Context::from_file_contents(&contents, false);
+    Ok((input.to_string(), context))
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Also generates a `test_examples` test that runs every part against the examples of the day.
+///
+/// Parts either take the input only, `fn(&str) -> Option<T>`, or additionally receive the [`Context`]
+/// of the input, `fn(&str, &Context) -> Option<T>`.
+///
+/// Alternative implementations of a part can be registered as named variants. They are checked
+/// against the examples along with the part and compared to it by `cargo compare`:
+///
+/// ```ignore
+/// advent_of_code::solution!(2, variants: {
+///     2 => ["string" => part_two_string],
+/// });
+/// ```
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2];);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [part_one, 1];);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [part_two, 2];);
+    };
+    ($day:expr, variants: { $( $vpart:literal => [ $( $vname:literal => $vfunc:expr ),* $(,)? ] ),* $(,)? }) => {
+        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]; $( $( [$vfunc, $vpart, $vname] )* )*);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@impl $day:expr, $( [$func:expr, $part:expr] )*; $( [$vfunc:expr, $vpart:expr, $vname:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
 
@@ -61,8 +123,65 @@ macro_rules! solution {
 
         fn main() {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            let args = $crate::template::cli::SolutionArgs::get();
+            if args.examples {
+                print_example_results(&example_results());
+                return;
+            }
+            let (input, context) = $crate::template::read_input(DAY, args.input.as_ref())
+                .unwrap_or_else(|err| {
+                    eprintln!("{err}");
+                    std::process::exit(1);
+                });
+            if args.variants {
+                $( run_variant(
+                    |input: &str| $crate::template::Part::call(&$func, input, &context),
+                    input.as_str(),
+                    $part,
+                    stringify!($func),
+                ); )*
+                $( run_variant(
+                    |input: &str| $crate::template::Part::call(&$vfunc, input, &context),
+                    input.as_str(),
+                    $vpart,
+                    $vname,
+                ); )*
+                return;
+            }
+            $( run_part(
+                |input: &str| $crate::template::Part::call(&$func, input, &context),
+                input.as_str(),
+                DAY,
+                $part,
+            ); )*
+            exit_on_failure();
+        }
+
+        /// Runs every part and variant against the examples in `data/examples/NN/*.txt` that declare an answer for it.
+        fn example_results() -> Vec<$crate::template::examples::ExampleResult> {
+            use $crate::template::examples::*;
+            let examples = read_examples(DAY);
+            let mut results = vec![];
+            $( results.extend(run_examples(&examples, $part, |input, context| {
+                $crate::template::Part::call(&$func, input, context)
+            })); )*
+            $( results.extend(
+                run_examples(&examples, $vpart, |input, context| {
+                    $crate::template::Part::call(&$vfunc, input, context)
+                })
+                .into_iter()
+                .map(|result| ExampleResult {
+                    example: format!("{} [{}]", result.example, $vname),
+                    ..result
+                }),
+            ); )*
+            results
+        }
+
+        #[cfg(test)]
+        #[test]
+        fn test_examples() {
+            $crate::template::examples::assert_examples(DAY, &example_results());
         }
     };
 }
@@ -0,0 +1,277 @@
+/// Reads the puzzle descriptions that `cargo download` stores as markdown in `data/puzzles/NN.md`.
+/// Descriptions saved as the HTML of the puzzle page are read as well.
+use std::fs;
+
+use crate::template::Day;
+use crate::template::aoc_cli::get_puzzle_path;
+
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    pub title: Option<String>,
+    /// The input of the example: the first code block after "For example", or the first code block.
+    pub example_input: Option<String>,
+    /// The answer to the example of each part: the last emphasized code in the description of the part.
+    pub example_answers: [Option<String>; 2],
+}
+
+impl Puzzle {
+    /// Reads the puzzle of `day`. Returns `None` if it was not downloaded yet.
+    pub fn read(day: Day) -> Option<Self> {
+        read_markdown(day).map(|markdown| Self::parse(&markdown))
+    }
+
+    pub fn parse(contents: &str) -> Self {
+        let markdown = to_markdown(contents);
+
+        Self {
+            title: markdown.lines().find_map(parse_title),
+            example_input: example_input(&markdown),
+            example_answers: split_parts(&markdown)
+                .map(|part| part.and_then(|part| emphasized_code(part).pop())),
+        }
+    }
+
+    /// The contents of an example file: the example answers and `params` in the front matter, followed by
+    /// the example input.
+    pub fn example_file(&self, params: &[(&str, &str)]) -> String {
+        let answers = self.example_answers.iter().zip(1..).map(|(answer, part)| {
+            answer
+                .as_ref()
+                .map_or_else(|| format!("part_{part}:"), |a| format!("part_{part}: {a}"))
+        });
+        let params = params
+            .iter()
+            .map(|(name, value)| format!("{name}: {value}"));
+        format!(
+            "---\n{}\n---\n{}",
+            answers.chain(params).collect::<Vec<_>>().join("\n"),
+            self.example_input.as_deref().unwrap_or_default()
+        )
+    }
+}
+
+/// Reads the description of `day` as markdown. Returns `None` if it was not downloaded yet.
+pub fn read_markdown(day: Day) -> Option<String> {
+    let contents = fs::read_to_string(get_puzzle_path(day)).ok()?;
+    Some(to_markdown(&contents))
+}
+
+fn to_markdown(contents: &str) -> String {
+    if contents.trim_start().starts_with('<') {
+        html_to_markdown(contents)
+    } else {
+        contents.to_string()
+    }
+}
+
+/// The text of a heading such as `--- Part Two ---`. aoc-cli writes them as `\--- Part Two ---`,
+/// followed by an underline.
+pub(crate) fn heading(line: &str) -> Option<&str> {
+    line.trim_start_matches(['#', '\\'])
+        .trim()
+        .strip_prefix("--- ")?
+        .strip_suffix(" ---")
+}
+
+fn parse_title(line: &str) -> Option<String> {
+    let (_, title) = heading(line)?.strip_prefix("Day ")?.split_once(": ")?;
+    Some(title.trim().into())
+}
+
+/// Splits the description at the `--- Part Two ---` heading. Part two is `None` until part one is solved.
+fn split_parts(markdown: &str) -> [Option<&str>; 2] {
+    let mut offset = 0;
+    for line in markdown.split_inclusive('\n') {
+        if heading(line) == Some("Part Two") {
+            return [Some(&markdown[..offset]), Some(&markdown[offset..])];
+        }
+        offset += line.len();
+    }
+    [Some(markdown), None]
+}
+
+pub(crate) fn is_fence(line: &str) -> bool {
+    line.trim_start().starts_with("```")
+}
+
+/// Picks the first code block that follows a mention of "for example", falling back to the first code block.
+fn example_input(markdown: &str) -> Option<String> {
+    let mut blocks = vec![];
+    let mut prose = String::new();
+    let mut block: Option<Vec<&str>> = None;
+
+    for line in markdown.lines() {
+        match (&mut block, is_fence(line)) {
+            (None, true) => block = Some(vec![]),
+            (None, false) => prose.push_str(&format!("{line}\n")),
+            (Some(lines), false) => lines.push(line),
+            (Some(lines), true) => {
+                let input: String = lines.iter().map(|line| format!("{line}\n")).collect();
+                let follows_example = prose.to_lowercase().contains("for example");
+                blocks.push((follows_example, input));
+                prose.clear();
+                block = None;
+            }
+        }
+    }
+
+    let first = blocks.first().map(|(_, input)| input.clone());
+    blocks
+        .into_iter()
+        .find_map(|(follows_example, input)| follows_example.then_some(input))
+        .or(first)
+}
+
+/// Collects emphasized inline code outside of code blocks, written either as `` `*42*` `` or `` *`42`* ``.
+fn emphasized_code(text: &str) -> Vec<String> {
+    let mut in_code_block = false;
+    let mut found = vec![];
+
+    for line in text.lines() {
+        if is_fence(line) {
+            in_code_block = !in_code_block;
+            continue;
+        }
+        if in_code_block {
+            continue;
+        }
+
+        // every odd segment is the content of an inline code span, if it is closed.
+        let segments: Vec<&str> = line.split('`').collect();
+        for i in (1..segments.len().saturating_sub(1)).step_by(2) {
+            let code = segments[i];
+            let inner = code
+                .strip_prefix('*')
+                .and_then(|code| code.strip_suffix('*'))
+                .filter(|inner| !inner.is_empty());
+            if let Some(inner) = inner {
+                found.push(inner.to_string());
+            } else if segments[i - 1].ends_with('*') && segments[i + 1].starts_with('*') {
+                found.push(code.to_string());
+            }
+        }
+    }
+
+    found
+}
+
+/// Converts the subset of HTML used on puzzle pages to the markdown that aoc-cli writes.
+fn html_to_markdown(html: &str) -> String {
+    let mut markdown = String::new();
+    let mut in_pre = false;
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        push_text(&mut markdown, &rest[..start], in_pre);
+        let Some(end) = rest[start..].find('>') else {
+            rest = &rest[start..];
+            break;
+        };
+        let tag = &rest[start + 1..start + end];
+        rest = &rest[start + end + 1..];
+
+        let (is_closing, name) = match tag.strip_prefix('/') {
+            Some(name) => (true, name),
+            None => (false, tag),
+        };
+        let name = name.split_whitespace().next().unwrap_or_default();
+
+        match (name, is_closing) {
+            ("h2", false) => markdown.push_str("\n## "),
+            ("h2" | "p" | "ul", true) => markdown.push_str("\n\n"),
+            ("pre", false) => {
+                in_pre = true;
+                markdown.push_str("```\n");
+            }
+            ("pre", true) => {
+                in_pre = false;
+                if !markdown.ends_with('\n') {
+                    markdown.push('\n');
+                }
+                markdown.push_str("```\n\n");
+            }
+            ("code", _) if !in_pre => markdown.push('`'),
+            ("em", _) if !in_pre => markdown.push('*'),
+            ("li", false) => markdown.push_str("- "),
+            ("li", true) => markdown.push('\n'),
+            _ => {}
+        }
+    }
+    push_text(&mut markdown, rest, in_pre);
+
+    markdown
+}
+
+fn push_text(markdown: &mut String, text: &str, in_pre: bool) {
+    let text = text
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+    if in_pre {
+        markdown.push_str(&text);
+    } else {
+        // newlines between tags are formatting, the text of a paragraph stays on one line.
+        markdown.push_str(&text.replace('\n', " ").replace("  ", " "));
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Puzzle;
+
+    const MARKDOWN: &str = include_str!("fixtures/puzzle.md");
+    const HTML: &str = include_str!("fixtures/puzzle.html");
+
+    fn expected() -> Puzzle {
+        Puzzle {
+            title: Some("Sonar Snowdrift".into()),
+            example_input: Some("199\n200\n208\n210\n200\n".into()),
+            example_answers: [Some("3".into()), Some("1".into())],
+        }
+    }
+
+    #[test]
+    fn parses_markdown() {
+        assert_eq!(Puzzle::parse(MARKDOWN), expected());
+    }
+
+    #[test]
+    fn parses_html() {
+        assert_eq!(Puzzle::parse(HTML), expected());
+    }
+
+    #[test]
+    fn handles_unsolved_part_one() {
+        let (part_one, _) = MARKDOWN.split_once("Your puzzle").unwrap();
+        let puzzle = Puzzle::parse(&part_one.replace("\\---", "## ---"));
+        assert_eq!(puzzle.title.as_deref(), Some("Sonar Snowdrift"));
+        assert_eq!(puzzle.example_answers, [Some("3".into()), None]);
+        assert_eq!(Puzzle::parse("no puzzle here"), Puzzle::default());
+    }
+
+    #[test]
+    fn falls_back_to_first_code_block() {
+        let puzzle =
+            Puzzle::parse("Consider this list:\n\n```\n1\n2\n```\n\nFor example, `*3*`.\n");
+        assert_eq!(puzzle.example_input.as_deref(), Some("1\n2\n"));
+    }
+
+    #[test]
+    fn renders_example_file() {
+        assert_eq!(
+            expected().example_file(&[]),
+            "---\npart_1: 3\npart_2: 1\n---\n199\n200\n208\n210\n200\n"
+        );
+        assert_eq!(
+            Puzzle::default().example_file(&[("connections", "10")]),
+            "---\npart_1:\npart_2:\nconnections: 10\n---\n"
+        );
+        assert_eq!(
+            Puzzle::default().example_file(&[]),
+            "---\npart_1:\npart_2:\n---\n"
+        );
+    }
+}
@@ -1,12 +1,22 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+///
+/// The table can be configured with environment variables, e.g. in the `[env]` section of `.cargo/config.toml`:
+///  - `AOC_README_TITLE`: title of the table, defaults to `Benchmarks`.
+///  - `AOC_README_COLUMNS`: comma separated list of `part_1`, `part_2`, `memory_1`, `memory_2`, `total` and `bar`.
+///  - `AOC_README_UNIT`: `auto`, `ns`, `µs` (or `us`), `ms` or `s`.
+///  - `AOC_README_SORT`: `day`, `part_1`, `part_2` or `total`. Sorting by time puts the slowest day first.
+///  - `AOC_README_LINK`: link of a day, where `{day}` is replaced with the padded day, e.g. `./src/bin/{day}.rs`.
+use std::{env, fs, io, str::FromStr};
 
 use crate::template::Day;
-use crate::template::timings::Timings;
+use crate::template::timings::{PartTiming, Timing, Timings};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
+/// Width of a relative bar in characters.
+const BAR_WIDTH: usize = 20;
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
@@ -30,13 +40,13 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
-        return Err(Error::Parser(
-            "{}: too many occurences of marker in README.".into(),
-        ));
+        return Err(Error::Parser(format!(
+            "{marker}: too many occurences of marker in README."
+        )));
     }
 
     let pos_start = matches
@@ -52,25 +62,266 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+/// Replaces the section between the two occurrences of `marker` with `table`, which includes the markers.
+pub(crate) fn replace_table(readme: &mut String, marker: &str, table: &str) -> Result<(), Error> {
+    let positions = locate_table(readme, marker)?;
+    readme.replace_range(positions.pos_start..positions.pos_end, table);
+    Ok(())
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Time(u8),
+    Memory(u8),
+    Total,
+    /// Total time relative to the slowest day.
+    Bar,
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "part_1" => Ok(Column::Time(1)),
+            "part_2" => Ok(Column::Time(2)),
+            "memory_1" => Ok(Column::Memory(1)),
+            "memory_2" => Ok(Column::Memory(2)),
+            "total" => Ok(Column::Total),
+            "bar" => Ok(Column::Bar),
+            x => Err(format!("unknown column `{x}`")),
+        }
+    }
+}
+
+impl Column {
+    fn header(self) -> String {
+        match self {
+            Column::Time(part) => format!("Part {part}"),
+            Column::Memory(part) => format!("Part {part} Memory"),
+            Column::Total => "Total".into(),
+            Column::Bar => "Relative".into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unit {
+    /// Picks a unit per value, like `Duration`'s `Debug` implementation.
+    Auto,
+    Nanos,
+    Micros,
+    Millis,
+    Seconds,
+}
+
+impl FromStr for Unit {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "auto" => Ok(Unit::Auto),
+            "ns" => Ok(Unit::Nanos),
+            "µs" | "us" => Ok(Unit::Micros),
+            "ms" => Ok(Unit::Millis),
+            "s" => Ok(Unit::Seconds),
+            x => Err(format!("unknown unit `{x}`")),
+        }
+    }
+}
+
+impl Unit {
+    fn format(self, timing: &PartTiming) -> String {
+        let nanos = timing.mean_nanos;
+        match self {
+            Unit::Auto => timing.format(),
+            Unit::Nanos => format!("{nanos:.0}ns"),
+            Unit::Micros => format!("{:.1}µs", nanos / 1e3),
+            Unit::Millis => format!("{:.2}ms", nanos / 1e6),
+            Unit::Seconds => format!("{:.3}s", nanos / 1e9),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SortKey {
+    Day,
+    Part(u8),
+    Total,
+}
+
+impl FromStr for SortKey {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim() {
+            "day" => Ok(SortKey::Day),
+            "part_1" => Ok(SortKey::Part(1)),
+            "part_2" => Ok(SortKey::Part(2)),
+            "total" => Ok(SortKey::Total),
+            x => Err(format!("unknown sort key `{x}`")),
+        }
+    }
+}
+
+/// How the benchmark table is rendered.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TableConfig {
+    pub title: String,
+    /// The columns after the day column. `None` shows the time of each part, plus memory if any part was profiled.
+    pub columns: Option<Vec<Column>>,
+    pub unit: Unit,
+    pub sort: SortKey,
+    /// Link of each day, with `{day}` as placeholder. `None` links to the solution binary.
+    pub link: Option<String>,
+}
+
+impl Default for TableConfig {
+    fn default() -> Self {
+        Self {
+            title: "Benchmarks".into(),
+            columns: None,
+            unit: Unit::Auto,
+            sort: SortKey::Day,
+            link: None,
+        }
+    }
+}
+
+impl TableConfig {
+    /// Reads the configuration from `AOC_README_*` environment variables. Invalid values are reported and ignored.
+    pub fn from_env() -> Self {
+        fn var<T>(name: &str, parse: impl Fn(&str) -> Result<T, String>) -> Option<T> {
+            let value = env::var(name).ok()?;
+            parse(&value)
+                .map_err(|err| eprintln!("Ignoring invalid {name}: {err}."))
+                .ok()
+        }
+
+        let default = Self::default();
+        Self {
+            title: env::var("AOC_README_TITLE").unwrap_or(default.title),
+            columns: var("AOC_README_COLUMNS", |value| {
+                value.split(',').map(Column::from_str).collect()
+            })
+            .or(default.columns),
+            unit: var("AOC_README_UNIT", Unit::from_str).unwrap_or(default.unit),
+            sort: var("AOC_README_SORT", SortKey::from_str).unwrap_or(default.sort),
+            link: env::var("AOC_README_LINK").ok().or(default.link),
+        }
+    }
+
+    fn link_for(&self, day: Day) -> String {
+        self.link.as_ref().map_or_else(
+            || get_path_for_bin(day),
+            |link| link.replace("{day}", &day.to_string()),
+        )
+    }
+
+    fn columns_for(&self, timings: &Timings) -> Vec<Column> {
+        self.columns.clone().unwrap_or_else(|| {
+            if timings.has_memory_stats() {
+                vec![
+                    Column::Time(1),
+                    Column::Memory(1),
+                    Column::Time(2),
+                    Column::Memory(2),
+                ]
+            } else {
+                vec![Column::Time(1), Column::Time(2)]
+            }
+        })
+    }
+}
+
+fn part(timing: &Timing, part: u8) -> Option<&PartTiming> {
+    match part {
+        1 => timing.part_1.as_ref(),
+        _ => timing.part_2.as_ref(),
+    }
+}
+
+/// Renders a bar of `BAR_WIDTH` characters, filled to `ratio`, with eighth blocks for the remainder.
+fn format_bar(ratio: f64) -> String {
+    const EIGHTHS: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    let eighths = (ratio.clamp(0.0, 1.0) * (BAR_WIDTH * 8) as f64).round() as usize;
+    let bar = "█".repeat(eighths / 8) + EIGHTHS[eighths % 8];
+    if bar.is_empty() { "▏".into() } else { bar }
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: Timings,
+    total_millis: f64,
+    config: &TableConfig,
+) -> String {
+    let header = format!("{prefix} {}", config.title);
+    let columns = config.columns_for(&timings);
+
+    let mut data = timings.data;
+    match config.sort {
+        SortKey::Day => data.sort_by_key(|t| t.day),
+        // slowest first, days without a timing for the part last.
+        SortKey::Part(p) => data.sort_by(|a, b| {
+            let nanos = |t: &Timing| part(t, p).map_or(-1.0, |p| p.mean_nanos);
+            nanos(b).total_cmp(&nanos(a))
+        }),
+        SortKey::Total => data.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos)),
+    }
+    let max_total = data.iter().map(|t| t.total_nanos).fold(0.0, f64::max);
 
     let mut lines: Vec<String> = vec![
         MARKER.into(),
         header,
         String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
+        format!(
+            "| Day | {} |",
+            columns
+                .iter()
+                .map(|c| c.header())
+                .collect::<Vec<_>>()
+                .join(" | ")
+        ),
+        format!("| :---: |{}", " :---: |".repeat(columns.len())),
     ];
 
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
+    for timing in &data {
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| match column {
+                Column::Time(p) => {
+                    let time = part(timing, *p).map_or_else(
+                        || {
+                            timing
+                                .failure_of(*p)
+                                .map_or_else(|| "-".into(), |failure| failure.to_string())
+                        },
+                        |p| config.unit.format(p),
+                    );
+                    format!("`{time}`")
+                }
+                Column::Memory(p) => {
+                    let memory = part(timing, *p)
+                        .and_then(|p| p.memory.as_ref())
+                        .map_or_else(|| "-".into(), |m| m.format());
+                    format!("`{memory}`")
+                }
+                Column::Total => {
+                    let total = PartTiming::from_mean(timing.total_nanos, 0);
+                    format!("`{}`", config.unit.format(&total))
+                }
+                Column::Bar if max_total > 0.0 => format_bar(timing.total_nanos / max_total),
+                Column::Bar => String::new(),
+            })
+            .collect();
+
         lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+            "| [Day {}]({}) | {} |",
             timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            config.link_for(timing.day),
+            cells.join(" | ")
         ));
     }
 
@@ -81,47 +332,56 @@ fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
-    Ok(())
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    config: &TableConfig,
+) -> Result<(), Error> {
+    let table = construct_table("##", timings, total_millis, config);
+    replace_table(s, MARKER, &table)
 }
 
 pub fn update(timings: Timings) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    update_content(&mut readme, timings, total_millis, &TableConfig::from_env())?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{MARKER, update_content};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{Column, MARKER, SortKey, TableConfig, Unit, update_content};
+    use crate::{
+        day,
+        template::timings::{Failure, MemoryStats, PartFailure, PartTiming, Timing, Timings},
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(PartTiming::from_mean(10e6, 10)),
+                    part_2: Some(PartTiming::from_mean(20e6, 10)),
                     total_nanos: 3e+10,
+                    failure: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(PartTiming::from_mean(30e6, 10)),
+                    part_2: Some(PartTiming::from_mean(40e6, 10)),
                     total_nanos: 7e+10,
+                    failure: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
-                    part_2: Some("50ms".into()),
+                    part_1: Some(PartTiming::from_mean(40e6, 10)),
+                    part_2: Some(PartTiming::from_mean(50e6, 10)),
                     total_nanos: 9e+10,
+                    failure: None,
                 },
             ],
         }
@@ -131,28 +391,28 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +420,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0, &TableConfig::default()).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -168,10 +428,10 @@ mod tests {
             "## Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `20.0ms` |",
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `40.0ms` |",
+            "| [Day 4](./src/bin/04.rs) | `40.0ms` | `50.0ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +440,102 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn formats_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1.as_mut().unwrap().memory = Some(MemoryStats {
+            peak_bytes: 1536,
+            total_allocations: 12,
+            total_bytes: 4096,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableConfig::default()).unwrap();
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(
+            lines[3],
+            "| Day | Part 1 | Part 1 Memory | Part 2 | Part 2 Memory |"
+        );
+        assert_eq!(
+            lines[5],
+            "| [Day 1](./src/bin/01.rs) | `10.0ms` | `1.5 KiB (12 allocs)` | `20.0ms` | `-` |"
+        );
+        assert_eq!(
+            lines[6],
+            "| [Day 2](./src/bin/02.rs) | `30.0ms` | `-` | `40.0ms` | `-` |"
+        );
+    }
+
+    #[test]
+    fn formats_configured_columns() {
+        let config = TableConfig {
+            title: "Timings".into(),
+            columns: Some(vec![Column::Total, Column::Bar]),
+            unit: Unit::Millis,
+            sort: SortKey::Total,
+            link: Some("https://example.com/{day}".into()),
+        };
+        let mut timings = get_mock_timings();
+        for (timing, total_nanos) in timings.data.iter_mut().zip([30e6, 70e6, 35e6]) {
+            timing.total_nanos = total_nanos;
+        }
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &config).unwrap();
+        let lines: Vec<&str> = s.lines().collect();
+        assert_eq!(
+            lines[1..8],
+            [
+                "## Timings",
+                "",
+                "| Day | Total | Relative |",
+                "| :---: | :---: | :---: |",
+                "| [Day 2](https://example.com/02) | `70.00ms` | ████████████████████ |",
+                "| [Day 4](https://example.com/04) | `35.00ms` | ██████████ |",
+                "| [Day 1](https://example.com/01) | `30.00ms` | ████████▋ |",
+            ]
+        );
+    }
+
+    #[test]
+    fn sorts_by_part() {
+        let config = TableConfig {
+            sort: SortKey::Part(2),
+            ..TableConfig::default()
+        };
+        let mut timings = get_mock_timings();
+        timings.data[1].part_2 = None;
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &config).unwrap();
+        let days: Vec<&str> = s
+            .lines()
+            .filter_map(|l| l.strip_prefix("| [Day "))
+            .map(|l| &l[..1])
+            .collect();
+        assert_eq!(days, ["4", "1", "2"]);
+    }
+
+    #[test]
+    fn formats_failed_parts() {
+        let mut timings = get_mock_timings();
+        timings.data[2].part_2 = None;
+        timings.data[2].failure = Some(PartFailure {
+            part: 2,
+            failure: Failure::Timeout,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, &TableConfig::default()).unwrap();
+        assert!(s.contains("| [Day 4](./src/bin/04.rs) | `40.0ms` | `timed out` |"));
+    }
+
+    #[test]
+    fn parses_config_values() {
+        assert_eq!("memory_2".parse(), Ok(Column::Memory(2)));
+        assert_eq!("us".parse(), Ok(Unit::Micros));
+        assert_eq!("total".parse(), Ok(SortKey::Total));
+        assert!("foo".parse::<Column>().is_err());
+    }
 }
@@ -0,0 +1,168 @@
+/// Module that renders the stars table of the readme, in the format of `aoc-readme-stars`.
+/// A part has a star if its answer was recorded in `data/answers.json`, or if the table already lists it.
+use std::{collections::BTreeMap, env, fs};
+
+use crate::template::Day;
+use crate::template::answers::Answers;
+use crate::template::readme_benchmarks::{Error, replace_table};
+
+static MARKER: &str = "<!--- advent_readme_stars table --->";
+
+static STAR: &str = "⭐";
+
+/// Which parts of each day were solved.
+pub type Stars = BTreeMap<Day, [bool; 2]>;
+
+/// Reads the stars of the table between the markers in `readme`. Returns no stars if the table is missing.
+fn existing_stars(readme: &str) -> Stars {
+    let mut stars = Stars::new();
+
+    let table = readme.split(MARKER).nth(1).unwrap_or_default();
+    for line in table.lines() {
+        let Some(row) = line.strip_prefix("| [Day ") else {
+            continue;
+        };
+        let Some(day) = row
+            .split(']')
+            .next()
+            .and_then(|day| day.parse().ok())
+            .and_then(Day::new)
+        else {
+            continue;
+        };
+        let cells: Vec<&str> = row.split('|').map(str::trim).collect();
+        stars.insert(
+            day,
+            [cells.get(1) == Some(&STAR), cells.get(2) == Some(&STAR)],
+        );
+    }
+
+    stars
+}
+
+/// Adds the stars of all recorded answers to `stars`.
+fn add_answers(stars: &mut Stars, answers: &Answers) {
+    for answer in &answers.data {
+        let entry = stars.entry(answer.day).or_default();
+        entry[0] |= answer.part_1.is_some();
+        entry[1] |= answer.part_2.is_some();
+    }
+}
+
+fn construct_table(year: Option<u16>, stars: &Stars) -> String {
+    let title = year.map_or_else(|| "Results".into(), |year| format!("{year} Results"));
+
+    let mut lines: Vec<String> = vec![
+        MARKER.into(),
+        format!("## {title}"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    let cell = |solved: bool| if solved { STAR } else { " " };
+
+    for (day, [part_1, part_2]) in stars {
+        if !part_1 && !part_2 {
+            continue;
+        }
+
+        let day = day.into_inner();
+        let link = year.map_or_else(
+            || format!("https://adventofcode.com/day/{day}"),
+            |year| format!("https://adventofcode.com/{year}/day/{day}"),
+        );
+        lines.push(format!(
+            "| [Day {day}]({link}) | {} | {} |",
+            cell(*part_1),
+            cell(*part_2)
+        ));
+    }
+
+    lines.push(MARKER.into());
+    lines.join("\n")
+}
+
+fn update_content(s: &mut String, answers: &Answers, year: Option<u16>) -> Result<Stars, Error> {
+    let mut stars = existing_stars(s);
+    add_answers(&mut stars, answers);
+    let table = construct_table(year, &stars);
+    replace_table(s, MARKER, &table)?;
+    Ok(stars)
+}
+
+/// Regenerates the stars table of the readme. Returns the stars it lists.
+pub fn update(answers: &Answers) -> Result<Stars, Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    let year = env::var("AOC_YEAR").ok().and_then(|year| year.parse().ok());
+    let stars = update_content(&mut readme, answers, year)?;
+    fs::write(path, &readme)?;
+    Ok(stars)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use crate::day;
+    use crate::template::answers::Answers;
+
+    use super::{MARKER, update_content};
+
+    #[test]
+    fn renders_recorded_answers() {
+        let mut answers = Answers::default();
+        answers.record(day!(2), 1, "5");
+        answers.record(day!(1), 1, "3");
+        answers.record(day!(1), 2, "4");
+
+        let mut s = format!("foo\n{MARKER}\n{MARKER}\nbar");
+        update_content(&mut s, &answers, Some(2025)).unwrap();
+
+        assert_eq!(
+            s,
+            [
+                "foo",
+                MARKER,
+                "## 2025 Results",
+                "",
+                "| Day | Part 1 | Part 2 |",
+                "| :---: | :---: | :---: |",
+                "| [Day 1](https://adventofcode.com/2025/day/1) | ⭐ | ⭐ |",
+                "| [Day 2](https://adventofcode.com/2025/day/2) | ⭐ |   |",
+                MARKER,
+                "bar",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn keeps_existing_stars() {
+        let mut answers = Answers::default();
+        answers.record(day!(10), 2, "7");
+
+        let mut s = [
+            MARKER,
+            "## Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 10](https://adventofcode.com/day/10) | ⭐ |   |",
+            "| [Day 11](https://adventofcode.com/day/11) | ⭐ | ⭐ |",
+            MARKER,
+        ]
+        .join("\n");
+        let stars = update_content(&mut s, &answers, None).unwrap();
+
+        assert_eq!(stars[&day!(10)], [true, true]);
+        assert_eq!(stars[&day!(11)], [true, true]);
+        assert!(s.contains("| [Day 10](https://adventofcode.com/day/10) | ⭐ | ⭐ |"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_marker_not_present() {
+        let mut s = "# readme".to_string();
+        update_content(&mut s, &Answers::default(), None).unwrap();
+    }
+}
@@ -0,0 +1,383 @@
+/// Collects the outcome of every part of a `cargo all` or `cargo time` run, for the final summary
+/// and for JUnit XML or TAP reports that CI can gate on.
+use std::fs;
+
+use crate::template::answers::Answers;
+use crate::template::examples::{NONE_MARKER, escape, unescape};
+use crate::template::{ANSI_BOLD, ANSI_RESET, Day};
+
+static RESULT_PREFIX: &str = "Part result:";
+
+/// Serializes the answer of a part to a single line, so it can be passed from a solution binary to the runner.
+pub fn to_line(part: u8, answer: Option<&str>) -> String {
+    format!(
+        "{RESULT_PREFIX}\t{part}\t{}",
+        answer.map_or_else(|| NONE_MARKER.into(), escape)
+    )
+}
+
+/// Parses a line written by [`to_line`] into the part and its answer. Returns `None` for any other line.
+pub fn from_line(line: &str) -> Option<(u8, Option<String>)> {
+    let mut fields = line
+        .strip_prefix(RESULT_PREFIX)?
+        .strip_prefix('\t')?
+        .split('\t');
+    let part = fields.next()?.parse().ok()?;
+    let answer = fields.next()?;
+    Some((part, (answer != NONE_MARKER).then(|| unescape(answer))))
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Passed,
+    /// The part returned `None` and no answer was accepted yet, e.g. because it was just scaffolded.
+    Unsolved,
+    /// The answer differs from the one accepted by Advent of Code, or is `None` although one was accepted.
+    Mismatch {
+        expected: String,
+    },
+    /// The part did not finish, e.g. because it crashed or timed out.
+    Failed(String),
+    /// The part was not run, e.g. because the input is missing.
+    Skipped(String),
+}
+
+impl Status {
+    /// Checks the answer of a part against the answer recorded in `data/answers.json`, if there is one.
+    pub(crate) fn check(answers: &Answers, day: Day, part: u8, answer: Option<&str>) -> Self {
+        let expected = answers.get(day).and_then(|a| a.part(part));
+        match (answer, expected) {
+            (None, None) => Status::Unsolved,
+            (answer, Some(expected)) if answer != Some(expected) => Status::Mismatch {
+                expected: expected.into(),
+            },
+            _ => Status::Passed,
+        }
+    }
+
+    /// Whether the part makes the run fail. Unsolved parts do not, so that a fresh scaffold passes.
+    pub fn is_failure(&self) -> bool {
+        !matches!(self, Status::Passed | Status::Unsolved | Status::Skipped(_))
+    }
+
+    /// Whether the part is reported as skipped by JUnit and TAP.
+    fn is_skipped(&self) -> bool {
+        matches!(self, Status::Unsolved | Status::Skipped(_))
+    }
+}
+
+/// The outcome of a single part.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PartReport {
+    pub day: Day,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub status: Status,
+}
+
+impl PartReport {
+    fn name(&self) -> String {
+        format!("day {} part {}", self.day, self.part)
+    }
+
+    /// Describes why the part failed or was skipped.
+    fn message(&self) -> String {
+        match &self.status {
+            Status::Passed => "passed".into(),
+            Status::Unsolved => "returned None".into(),
+            Status::Mismatch { expected } => format!(
+                "expected {expected}, got {}",
+                self.answer.as_deref().unwrap_or("None")
+            ),
+            Status::Failed(reason) | Status::Skipped(reason) => reason.clone(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Report {
+    pub parts: Vec<PartReport>,
+}
+
+impl Report {
+    pub fn push(&mut self, day: Day, part: u8, answer: Option<String>, status: Status) {
+        self.parts.push(PartReport {
+            day,
+            part,
+            answer,
+            status,
+        });
+    }
+
+    pub fn has_failures(&self) -> bool {
+        self.parts.iter().any(|p| p.status.is_failure())
+    }
+
+    fn count(&self, f: impl Fn(&Status) -> bool) -> usize {
+        self.parts.iter().filter(|p| f(&p.status)).count()
+    }
+
+    fn counts(&self) -> (usize, usize, usize) {
+        (
+            self.count(|s| *s == Status::Passed),
+            self.count(Status::is_failure),
+            self.count(Status::is_skipped),
+        )
+    }
+
+    /// Renders one row per day and one column per part, followed by the reason of every failure.
+    pub fn summary(&self) -> String {
+        let mut days: Vec<Day> = self.parts.iter().map(|p| p.day).collect();
+        days.sort_unstable();
+        days.dedup();
+
+        let mut lines = vec![
+            format!("{ANSI_BOLD}Summary{ANSI_RESET}"),
+            "-------".into(),
+            "Day  Part 1  Part 2".into(),
+        ];
+
+        for day in days {
+            let cells = [1, 2].map(|part| {
+                self.parts
+                    .iter()
+                    .find(|p| p.day == day && p.part == part)
+                    .map_or(" ", |p| match p.status {
+                        Status::Passed => "✔",
+                        Status::Unsolved => "?",
+                        Status::Skipped(_) => "-",
+                        _ => "✖",
+                    })
+            });
+            let row = format!("{day}   {:6}  {}", cells[0], cells[1]);
+            lines.push(row.trim_end().into());
+        }
+
+        let failures: Vec<String> = self
+            .parts
+            .iter()
+            .filter(|p| p.status.is_failure())
+            .map(|p| format!("  {}: {}", p.name(), p.message()))
+            .collect();
+        if !failures.is_empty() {
+            lines.push(String::new());
+            lines.extend(failures);
+        }
+
+        let (passed, failed, _) = self.counts();
+        let unsolved = self.count(|s| *s == Status::Unsolved);
+        let skipped = self.count(|s| matches!(s, Status::Skipped(_)));
+        lines.push(String::new());
+        lines.push(format!(
+            "{ANSI_BOLD}Parts:{ANSI_RESET} {passed} passed, {failed} failed, {unsolved} unsolved, {skipped} skipped"
+        ));
+        lines.join("\n")
+    }
+
+    pub fn to_junit(&self) -> String {
+        let (_, failed, skipped) = self.counts();
+        let mut lines = vec![
+            r#"<?xml version="1.0" encoding="UTF-8"?>"#.to_string(),
+            format!(
+                r#"<testsuite name="advent_of_code" tests="{}" failures="{failed}" skipped="{skipped}">"#,
+                self.parts.len()
+            ),
+        ];
+
+        for part in &self.parts {
+            let testcase = format!(
+                r#"  <testcase classname="day_{}" name="part {}""#,
+                part.day, part.part
+            );
+            let message = escape_xml(&part.message());
+            lines.push(match &part.status {
+                Status::Passed => format!("{testcase}/>"),
+                status if status.is_skipped() => {
+                    format!(r#"{testcase}><skipped message="{message}"/></testcase>"#)
+                }
+                _ => format!(r#"{testcase}><failure message="{message}"/></testcase>"#),
+            });
+        }
+
+        lines.push("</testsuite>".into());
+        lines.push(String::new());
+        lines.join("\n")
+    }
+
+    pub fn to_tap(&self) -> String {
+        let mut lines = vec![
+            "TAP version 13".to_string(),
+            format!("1..{}", self.parts.len()),
+        ];
+
+        for (index, part) in self.parts.iter().enumerate() {
+            let number = index + 1;
+            let name = part.name();
+            match &part.status {
+                Status::Passed => lines.push(format!("ok {number} - {name}")),
+                status if status.is_skipped() => {
+                    lines.push(format!("ok {number} - {name} # SKIP {}", part.message()));
+                }
+                _ => {
+                    lines.push(format!("not ok {number} - {name}"));
+                    lines.push("  ---".into());
+                    lines.push(format!("  message: {:?}", part.message()));
+                    lines.push("  ...".into());
+                }
+            }
+        }
+
+        lines.push(String::new());
+        lines.join("\n")
+    }
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', "&#10;")
+}
+
+/// Where to write the reports of a run, set by `--junit` and `--tap`.
+#[derive(Clone, Debug, Default)]
+pub struct ReportOptions {
+    pub junit: Option<String>,
+    pub tap: Option<String>,
+}
+
+impl ReportOptions {
+    /// Writes the requested reports. Returns `false` if one of them could not be written.
+    pub fn write(&self, report: &Report) -> bool {
+        let mut is_ok = true;
+        for (path, contents) in [
+            (&self.junit, Report::to_junit as fn(&Report) -> String),
+            (&self.tap, Report::to_tap),
+        ] {
+            let Some(path) = path else {
+                continue;
+            };
+            match fs::write(path, contents(report)) {
+                Ok(()) => println!("Wrote report to \"{path}\"."),
+                Err(err) => {
+                    eprintln!("Failed to write report to \"{path}\": {err}");
+                    is_ok = false;
+                }
+            }
+        }
+        is_ok
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Report, Status, from_line, to_line};
+    use crate::day;
+    use crate::template::answers::Answers;
+
+    fn get_mock_report() -> Report {
+        let mut report = Report::default();
+        report.push(day!(1), 1, Some("42".into()), Status::Passed);
+        report.push(
+            day!(1),
+            2,
+            Some("7".into()),
+            Status::Mismatch {
+                expected: "<8>".into(),
+            },
+        );
+        report.push(
+            day!(2),
+            1,
+            None,
+            Status::Mismatch {
+                expected: "3".into(),
+            },
+        );
+        report.push(day!(2), 2, None, Status::Unsolved);
+        report.push(day!(3), 1, None, Status::Skipped("no input".into()));
+        report
+    }
+
+    #[test]
+    fn round_trips_result_lines() {
+        assert_eq!(
+            from_line(&to_line(2, Some("#.\n.#"))),
+            Some((2, Some("#.\n.#".into())))
+        );
+        assert_eq!(from_line(&to_line(1, None)), Some((1, None)));
+        assert_eq!(from_line("Part 1: 42"), None);
+    }
+
+    #[test]
+    fn checks_answers() {
+        let mut answers = Answers::default();
+        answers.record(day!(1), 1, "42");
+
+        assert_eq!(
+            Status::check(&answers, day!(1), 1, Some("42")),
+            Status::Passed
+        );
+        assert_eq!(
+            Status::check(&answers, day!(1), 1, Some("41")),
+            Status::Mismatch {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(
+            Status::check(&answers, day!(1), 2, Some("1")),
+            Status::Passed
+        );
+        assert_eq!(
+            Status::check(&answers, day!(1), 1, None),
+            Status::Mismatch {
+                expected: "42".into()
+            }
+        );
+        assert_eq!(Status::check(&answers, day!(1), 2, None), Status::Unsolved);
+        assert!(!Status::Unsolved.is_failure());
+    }
+
+    #[test]
+    fn summarizes_parts() {
+        let report = get_mock_report();
+        assert!(report.has_failures());
+
+        let summary = report.summary();
+        assert!(summary.contains("\n01   ✔       ✖\n02   ✖       ?\n03   -\n"));
+        assert!(summary.contains("  day 01 part 2: expected <8>, got 7"));
+        assert!(summary.contains("  day 02 part 1: expected 3, got None"));
+        assert!(!summary.contains("day 02 part 2"));
+        assert!(summary.ends_with("1 passed, 2 failed, 1 unsolved, 1 skipped"));
+
+        let mut report = Report::default();
+        report.push(day!(1), 1, Some("1".into()), Status::Passed);
+        report.push(day!(1), 2, None, Status::Unsolved);
+        assert!(!report.has_failures());
+    }
+
+    #[test]
+    fn renders_junit() {
+        let junit = get_mock_report().to_junit();
+        assert!(junit.contains(r#"tests="5" failures="2" skipped="2""#));
+        assert!(junit.contains(r#"<testcase classname="day_01" name="part 1"/>"#));
+        assert!(junit.contains(r#"<failure message="expected &lt;8&gt;, got 7"/>"#));
+        assert!(junit.contains(r#"<skipped message="no input"/>"#));
+        assert!(junit.contains(r#"<skipped message="returned None"/>"#));
+    }
+
+    #[test]
+    fn renders_tap() {
+        let tap = get_mock_report().to_tap();
+        let lines: Vec<&str> = tap.lines().collect();
+        assert_eq!(
+            lines[..3],
+            ["TAP version 13", "1..5", "ok 1 - day 01 part 1"]
+        );
+        assert_eq!(lines[3], "not ok 2 - day 01 part 2");
+        assert_eq!(lines[5], r#"  message: "expected <8>, got 7""#);
+        assert!(tap.contains("ok 4 - day 02 part 2 # SKIP returned None"));
+        assert!(tap.contains("ok 5 - day 03 part 1 # SKIP no input"));
+    }
+}
@@ -1,56 +1,279 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    io,
+    path::Path,
+    process::ExitStatus,
+    sync::{Mutex, mpsc},
+    thread,
+};
 
 use crate::template::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET, Day};
 
 use super::{
     all_days,
-    timings::{Timing, Timings},
+    answers::Answers,
+    limits::{self, Limits},
+    report::{Report, Status},
+    timings::{PartFailure, Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+/// Runs the solutions of `days_to_run`. Timed runs are always sequential, so that benchmarks remain comparable.
+/// Untimed runs use up to `jobs` concurrent children and print the buffered output of each day in order.
+/// Days that exceed a limit of `limits` are stopped, days that did not start before the total timeout are skipped.
+/// Returns the timings of timed runs and the outcome of every part.
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    jobs: usize,
+    limits: &Limits,
+) -> (Option<Timings>, Report) {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut skipped: Vec<Day> = vec![];
+    let mut report = Report::default();
+    let answers = Answers::read_from_file().unwrap_or_else(|err| {
+        eprintln!("warning: {err} Answers are not compared.");
+        Answers::default()
+    });
 
     let mut need_space = false;
 
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    limits.warn_if_unsupported();
+    let deadline = limits.deadline();
+    let is_skipped =
+        |day: Day| limits::is_past(deadline) && Path::new(&get_path_for_bin(day)).exists();
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    let print_header = |day: Day, need_space: &mut bool| {
+        if *need_space {
+            println!();
+        }
+        *need_space = true;
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+        println!("------");
+    };
 
-            if output.is_empty() {
+    if is_timed || jobs <= 1 {
+        for day in days {
+            if is_skipped(day) {
+                skipped.push(day);
+                continue;
+            }
+
+            print_header(day, &mut need_space);
+
+            let timeout = limits.day_timeout(deadline);
+            let output = match child_commands::run_solution(
+                day,
+                is_timed,
+                is_release,
+                timeout,
+                limits.memory_bytes,
+            ) {
+                Ok(output) => output,
+                Err(err) => {
+                    print_error(day, &err);
+                    report.push(day, 1, None, Status::Failed("failed to run".into()));
+                    continue;
+                }
+            };
+
+            let description = output.failure.map(|f| limits.describe(f, timeout));
+            report_day(
+                &mut report,
+                &answers,
+                day,
+                &output.results,
+                output.status,
+                output.failure.zip(description.clone()),
+            );
+
+            if let Some(description) = &description {
+                println!("{description}");
+            } else if output.lines.is_empty() {
                 println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
+                continue;
+            }
+
+            if is_timed {
+                let mut val = child_commands::parse_exec_time(&output.lines, day);
+                val.failure = output.failure;
                 timings.push(val);
             }
-        });
+        }
+    } else {
+        for_each_ordered(
+            days,
+            jobs,
+            |day| {
+                if is_skipped(day) {
+                    return (day, None);
+                }
+                let timeout = limits.day_timeout(deadline);
+                let output = child_commands::run_solution_buffered(
+                    day,
+                    is_release,
+                    timeout,
+                    limits.memory_bytes,
+                );
+                (day, Some((output, timeout)))
+            },
+            |(day, output)| {
+                let Some((output, timeout)) = output else {
+                    skipped.push(day);
+                    return;
+                };
+                print_header(day, &mut need_space);
+                match output {
+                    Ok(None) => println!("Not solved."),
+                    Ok(Some(output)) => {
+                        let description = output.failure.map(|f| limits.describe(f, timeout));
+                        print!("{}", output.stdout);
+                        eprint!("{}", output.stderr);
+                        if let Some(description) = &description {
+                            println!("{description}");
+                        } else if output.stdout.is_empty() {
+                            println!("Not solved.");
+                        }
+                        report_day(
+                            &mut report,
+                            &answers,
+                            day,
+                            &output.results,
+                            output.status,
+                            output.failure.zip(description),
+                        );
+                    }
+                    Err(err) => {
+                        print_error(day, &err);
+                        report.push(day, 1, None, Status::Failed("failed to run".into()));
+                    }
+                }
+            },
+        );
+    }
 
-    if is_timed {
+    if !skipped.is_empty() {
+        let days: Vec<String> = skipped.iter().map(ToString::to_string).collect();
+        println!(
+            "\n{ANSI_BOLD}Skipped:{ANSI_RESET} {} (the total timeout was reached)",
+            days.join(", ")
+        );
+        for day in skipped {
+            for part in [1, 2] {
+                report.push(day, part, None, Status::Skipped("total timeout".into()));
+            }
+        }
+    }
+
+    let timings = is_timed.then(|| {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
             "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
-    } else {
-        None
+        timings
+    });
+
+    (timings, report)
+}
+
+fn print_error(day: Day, err: &Error) {
+    match err {
+        Error::Failed(stderr) => eprintln!("Failed to run day {day}:\n{stderr}"),
+        err => eprintln!("Failed to run day {day}: {err:?}"),
+    }
+}
+
+/// Adds the outcome of each part of a day to `report`. Parts that did not report an answer
+/// were stopped by `failure`, crashed, or could not run as the day has no input.
+fn report_day(
+    report: &mut Report,
+    answers: &Answers,
+    day: Day,
+    results: &[(u8, Option<String>)],
+    status: Option<ExitStatus>,
+    failure: Option<(PartFailure, String)>,
+) {
+    for (part, answer) in results {
+        let status = Status::check(answers, day, *part, answer.as_deref());
+        report.push(day, *part, answer.clone(), status);
+    }
+
+    let missing_part = (1..=2).find(|part| !results.iter().any(|(p, _)| p == part));
+
+    if let Some((failure, description)) = failure {
+        report.push(day, failure.part, None, Status::Failed(description));
+    } else if let Some(status) = status.filter(|status| !status.success()) {
+        // solutions exit with code 1 after reporting a wrong or missing answer, or if their input is missing.
+        if status.code() == Some(1) && !results.is_empty() {
+            return;
+        }
+        if results.is_empty() && !Path::new(&get_path_for_input(day)).exists() {
+            for part in [1, 2] {
+                report.push(day, part, None, Status::Skipped("no input".into()));
+            }
+        } else if let Some(part) = missing_part {
+            report.push(
+                day,
+                part,
+                None,
+                Status::Failed(format!("crashed, {status}")),
+            );
+        }
     }
 }
 
+/// Maps `items` with `f` on up to `jobs` threads and passes the results to `on_result` in the order of `items`,
+/// as soon as all preceding results are available.
+pub(crate) fn for_each_ordered<T: Send, R: Send>(
+    items: Vec<T>,
+    jobs: usize,
+    f: impl Fn(T) -> R + Sync,
+    mut on_result: impl FnMut(R),
+) {
+    let queue = Mutex::new(items.into_iter().enumerate());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs.max(1) {
+            let sender = sender.clone();
+            let (queue, f) = (&queue, &f);
+            scope.spawn(move || {
+                loop {
+                    let Some((index, item)) = queue.lock().unwrap().next() else {
+                        break;
+                    };
+                    if sender.send((index, f(item))).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+        drop(sender);
+
+        let mut pending = BTreeMap::new();
+        let mut next = 0;
+        for (index, result) in receiver {
+            pending.insert(index, result);
+            while let Some(result) = pending.remove(&next) {
+                on_result(result);
+                next += 1;
+            }
+        }
+    });
+}
+
 #[allow(dead_code)]
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
     IO(io::Error),
+    /// The child process exited unsuccessfully, with the given stderr output.
+    Failed(String),
 }
 
 impl From<std::io::Error> for Error {
@@ -64,23 +287,291 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
+fn get_path_for_input(day: Day) -> String {
+    format!("./data/inputs/{day}.txt")
+}
+
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{Error, get_path_for_bin};
-    use crate::template::Day;
+    use crate::template::cli::SolutionArgs;
+    use crate::template::limits::{self, Outcome};
+    use crate::template::report;
+    use crate::template::timings::{MemoryStats, PartFailure, PartTiming, parse_duration};
+    use crate::template::{Day, examples::ExampleResult, variants::VariantResult};
     use std::{
+        collections::HashMap,
         io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
+        path::{Path, PathBuf},
+        process::{Command, ExitStatus, Stdio},
         thread,
+        time::Duration,
     };
+    use tinyjson::JsonValue;
+
+    /// Build the solution bin for a given day and return the path of its executable along with the build's stderr,
+    /// so that the solution can be supervised directly instead of through `cargo run`.
+    fn build_solution(day: Day, is_release: bool) -> Result<(PathBuf, String), Error> {
+        let day_padded = day.to_string();
+        let mut args = vec![
+            "build",
+            "--quiet",
+            "--bin",
+            &day_padded,
+            "--message-format=json-render-diagnostics",
+        ];
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+        if is_release {
+            args.push("--release");
+        }
+
+        let output = Command::new("cargo").args(&args).output()?;
+        let stderr = String::from_utf8_lossy(&output.stderr).into_owned();
+
+        if !output.status.success() {
+            return Err(Error::Failed(stderr));
+        }
+
+        // cargo reports every built artifact as a JSON message, the one of the bin carries its executable.
+        let executable = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(|line| line.parse::<JsonValue>().ok())
+            .find_map(|message| {
+                let message = message.get::<HashMap<String, JsonValue>>()?;
+                let target = message.get("target")?.get::<HashMap<String, JsonValue>>()?;
+                if target.get("name")?.get::<String>()? != &day_padded {
+                    return None;
+                }
+                message
+                    .get("executable")?
+                    .get::<String>()
+                    .map(PathBuf::from)
+            })
+            .ok_or_else(|| Error::Failed(format!("Could not find the executable of day {day}.")))?;
+
+        Ok((executable, stderr))
+    }
+
+    /// The output of a supervised run of a solution bin.
+    struct SupervisedRun {
+        /// Complete lines written to stdout.
+        stdout: Vec<String>,
+        /// Output written to stderr, empty if it was forwarded.
+        stderr: String,
+        results: Vec<(u8, Option<String>)>,
+        /// `None` if the run was stopped.
+        status: Option<ExitStatus>,
+        failure: Option<PartFailure>,
+    }
+
+    /// Runs the solution bin for a given day within the given limits.
+    /// If `forward` is set, output is printed while the solution runs, with the exception of statistics lines.
+    /// Returns `None` for days that have not been scaffolded yet.
+    fn run_supervised(
+        day: Day,
+        is_release: bool,
+        solution_args: &SolutionArgs,
+        timeout: Option<Duration>,
+        memory_bytes: Option<u64>,
+        forward: bool,
+    ) -> Result<Option<SupervisedRun>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok(None);
+        }
+
+        // build first, so that compile times do not count towards the timeout.
+        let (executable, build_stderr) = build_solution(day, is_release)?;
+        if forward {
+            eprint!("{build_stderr}");
+        }
+
+        // spawn child command with piped stdout/stderr.
+        let mut child = Command::new(executable)
+            .args(solution_args.to_args())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+
+        let mut stdout = BufReader::new(child.stdout.take().ok_or(Error::BrokenPipe)?);
+        let stderr = BufReader::new(child.stderr.take().ok_or(Error::BrokenPipe)?);
+
+        let stderr_thread = thread::spawn(move || {
+            let mut output = String::new();
+            for line in stderr.lines().map_while(Result::ok) {
+                if forward {
+                    eprintln!("{line}");
+                } else {
+                    output.push_str(&line);
+                    output.push('\n');
+                }
+            }
+            output
+        });
+
+        // a line without a trailing newline is the intermediate result of a part that is still running.
+        let stdout_thread = thread::spawn(move || {
+            let mut lines = vec![];
+            let mut partial = None;
+            let mut buffer = String::new();
+            while matches!(stdout.read_line(&mut buffer), Ok(n) if n > 0) {
+                if let Some(line) = buffer.strip_suffix('\n') {
+                    let is_protocol =
+                        PartTiming::from_line(line).is_some() || report::from_line(line).is_some();
+                    if forward && !is_protocol {
+                        println!("{line}");
+                    }
+                    lines.push(line.to_string());
+                } else {
+                    partial = Some(buffer.clone());
+                }
+                buffer.clear();
+            }
+            (lines, partial)
+        });
+
+        let outcome = limits::supervise(&mut child, timeout, memory_bytes)?;
+
+        let (mut lines, partial) = stdout_thread.join().unwrap();
+        let stderr = if forward {
+            stderr_thread.join().unwrap()
+        } else {
+            build_stderr + &stderr_thread.join().unwrap()
+        };
+
+        let results = lines.iter().filter_map(|l| report::from_line(l)).collect();
+
+        let status = match outcome {
+            Outcome::Exited(status) => status,
+            Outcome::Stopped(failure) => {
+                // a part is done once its final result line was written.
+                let part = (1..=2)
+                    .find(|part| {
+                        let prefix = format!("Part {part}:");
+                        !lines.iter().any(|line| line.starts_with(&prefix))
+                    })
+                    .unwrap_or(2);
+                return Ok(Some(SupervisedRun {
+                    stdout: lines,
+                    stderr,
+                    results,
+                    status: None,
+                    failure: Some(PartFailure { part, failure }),
+                }));
+            }
+        };
+
+        if let Some(partial) = partial {
+            if forward {
+                println!("{partial}");
+            }
+            lines.push(partial);
+        }
+
+        Ok(Some(SupervisedRun {
+            stdout: lines,
+            stderr,
+            results,
+            status: Some(status),
+            failure: None,
+        }))
+    }
+
+    /// The output of a solution run that was forwarded while the solution ran.
+    #[derive(Debug, Default)]
+    pub struct SolutionOutput {
+        pub lines: Vec<String>,
+        /// The answer of each part that finished.
+        pub results: Vec<(u8, Option<String>)>,
+        /// `None` if the run was stopped or the day has not been scaffolded yet.
+        pub status: Option<ExitStatus>,
+        /// Set if the run was stopped by a timeout or memory limit.
+        pub failure: Option<PartFailure>,
+    }
+
+    /// Run the solution bin for a given day, forwarding its output.
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_release: bool,
+        timeout: Option<Duration>,
+        memory_bytes: Option<u64>,
+    ) -> Result<SolutionOutput, Error> {
+        // mirror `--time` flag to child invocations and request machine readable statistics.
+        let solution_args = SolutionArgs {
+            time: is_timed,
+            stats: is_timed,
+            results: true,
+            ..SolutionArgs::default()
+        };
+
+        let run = run_supervised(day, is_release, &solution_args, timeout, memory_bytes, true)?;
+
+        Ok(
+            run.map_or_else(SolutionOutput::default, |run| SolutionOutput {
+                lines: run.stdout,
+                results: run.results,
+                status: run.status,
+                failure: run.failure,
+            }),
+        )
+    }
+
+    /// The captured output of a solution bin.
+    #[derive(Debug)]
+    pub struct BufferedOutput {
+        pub stdout: String,
+        pub stderr: String,
+        /// The answer of each part that finished.
+        pub results: Vec<(u8, Option<String>)>,
+        /// `None` if the run was stopped.
+        pub status: Option<ExitStatus>,
+        /// Set if the run was stopped by a timeout or memory limit.
+        pub failure: Option<PartFailure>,
+    }
+
+    /// Run the solution bin for a given day once, capturing its output instead of forwarding it.
+    /// Returns `None` for days that have not been scaffolded yet.
+    pub fn run_solution_buffered(
+        day: Day,
+        is_release: bool,
+        timeout: Option<Duration>,
+        memory_bytes: Option<u64>,
+    ) -> Result<Option<BufferedOutput>, Error> {
+        let solution_args = SolutionArgs {
+            results: true,
+            ..SolutionArgs::default()
+        };
+
+        let run = run_supervised(
+            day,
+            is_release,
+            &solution_args,
+            timeout,
+            memory_bytes,
+            false,
+        )?;
+
+        Ok(run.map(|run| BufferedOutput {
+            stdout: run
+                .stdout
+                .iter()
+                .filter(|line| report::from_line(line).is_none())
+                .map(|line| format!("{line}\n"))
+                .collect(),
+            stderr: run.stderr,
+            results: run.results,
+            status: run.status,
+            failure: run.failure,
+        }))
+    }
+
+    /// Run the solution bin for a given day against its examples.
+    /// Returns `None` for days that have not been scaffolded yet.
+    pub fn run_examples(day: Day, is_release: bool) -> Result<Option<Vec<ExampleResult>>, Error> {
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            return Ok(None);
         }
 
         let day_padded = day.to_string();
@@ -90,103 +581,178 @@ pub mod child_commands {
             args.push("--release");
         }
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+        let solution_args = SolutionArgs {
+            examples: true,
+            ..SolutionArgs::default()
+        };
+
+        let output = Command::new("cargo")
+            .args(&args)
+            .arg("--")
+            .args(solution_args.to_args())
+            .output()?;
+
+        if !output.status.success() {
+            return Err(Error::Failed(
+                String::from_utf8_lossy(&output.stderr).into_owned(),
+            ));
         }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        Ok(Some(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(ExampleResult::from_line)
+                .collect(),
+        ))
+    }
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+    /// Run the solution bin for a given day once with dhat heap profiling enabled.
+    /// Returns the memory statistics of each part, or `None` for days that have not been scaffolded yet.
+    pub fn run_memory_profile(day: Day) -> Result<Option<Vec<(u8, MemoryStats)>>, Error> {
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            return Ok(None);
+        }
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+        let day_padded = day.to_string();
+        let args = [
+            "run",
+            "--quiet",
+            "--profile",
+            "dhat",
+            "--features",
+            "dhat-heap",
+            "--bin",
+            &day_padded,
+        ];
+        let solution_args = SolutionArgs {
+            stats: true,
+            ..SolutionArgs::default()
+        };
 
-        let mut output = vec![];
+        let output = Command::new("cargo")
+            .args(args)
+            .arg("--")
+            .args(solution_args.to_args())
+            .output()?;
+
+        let stats: Vec<(u8, MemoryStats)> = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(PartTiming::from_line)
+            .filter_map(|(part, timing)| Some((part, timing.memory?)))
+            .collect();
+
+        // solutions exit unsuccessfully if a part returned `None`, which does not invalidate the statistics of the others.
+        if !output.status.success() && stats.is_empty() {
+            return Err(Error::Failed(
+                String::from_utf8_lossy(&output.stderr).into_owned(),
+            ));
+        }
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        Ok(Some(stats))
+    }
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+    /// Run all variants of the solution bin for a given day against the real input.
+    /// Always uses a release build, as the variants are benched.
+    pub fn run_variants(day: Day) -> Result<Option<Vec<VariantResult>>, Error> {
+        if !Path::new(&get_path_for_bin(day)).exists() {
+            return Ok(None);
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        let day_padded = day.to_string();
+        let args = ["run", "--quiet", "--release", "--bin", &day_padded];
+        let solution_args = SolutionArgs {
+            variants: true,
+            ..SolutionArgs::default()
+        };
+
+        let output = Command::new("cargo")
+            .args(args)
+            .arg("--")
+            .args(solution_args.to_args())
+            .output()?;
 
-        Ok(output)
+        if !output.status.success() {
+            return Err(Error::Failed(
+                String::from_utf8_lossy(&output.stderr).into_owned(),
+            ));
+        }
+
+        Ok(Some(
+            String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter_map(VariantResult::from_line)
+                .collect(),
+        ))
     }
 
+    /// Collects the timings of each part. Prefers the statistics lines written for `--stats`
+    /// and falls back to the human readable result lines.
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
+            failure: None,
         };
 
-        output
+        let mut stats = output
             .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
-
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+            .filter_map(|l| PartTiming::from_line(l))
+            .peekable();
+
+        let parts: Vec<(u8, PartTiming)> = if stats.peek().is_some() {
+            stats.collect()
+        } else {
+            output
+                .iter()
+                .filter_map(|l| {
+                    if !l.contains(" samples)") {
+                        return None;
+                    }
+
+                    let Some(timing) = parse_time(l) else {
+                        eprintln!("Could not parse timings from line: {l}");
+                        return None;
+                    };
+
+                    let part = l.split(':').next()?;
+                    let part = if part.contains("Part 1") {
+                        1
+                    } else if part.contains("Part 2") {
+                        2
+                    } else {
+                        return None;
+                    };
+                    Some((part, timing))
+                })
+                .collect()
+        };
 
-                timings.total_nanos += nanos;
-            });
+        for (part, timing) in parts {
+            timings.total_nanos += timing.mean_nanos;
+            match part {
+                1 => timings.part_1 = Some(timing),
+                2 => timings.part_2 = Some(timing),
+                _ => {}
+            }
+        }
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
+    fn parse_time(line: &str) -> Option<PartTiming> {
+        let (timing, samples) = line
             .split(" samples)")
             .next()?
             .split('(')
             .next_back()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+            .split_once('@')?;
 
-        Some((str_timing, parsed_timing))
+        Some(PartTiming::from_mean(
+            parse_duration(timing)?,
+            samples.trim().parse().ok()?,
+        ))
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -206,6 +772,7 @@ pub mod child_commands {
     #[cfg(feature = "test_lib")]
     mod tests {
         use super::parse_exec_time;
+        use crate::template::timings::PartTiming;
 
         use crate::day;
 
@@ -220,8 +787,10 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            let part_1 = res.part_1.unwrap();
+            assert_approx_eq!(part_1.mean_nanos, 74.13_f64);
+            assert_eq!(part_1.samples, 100000);
+            assert_approx_eq!(res.part_2.unwrap().mean_nanos, 74130000_f64);
         }
 
         #[test]
@@ -235,8 +804,8 @@ pub mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_approx_eq!(res.part_1.unwrap().mean_nanos, 2e9_f64);
+            assert_eq!(res.part_2.unwrap().samples, 1);
         }
 
         #[test]
@@ -253,5 +822,58 @@ pub mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn prefers_statistics_lines() {
+            let stats = PartTiming {
+                mean_nanos: 1500.0,
+                median_nanos: 1400.0,
+                min_nanos: 1000.0,
+                max_nanos: 3000.0,
+                std_dev_nanos: 250.5,
+                samples: 10,
+                memory: None,
+            };
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (1.5µs @ 10 samples)".into(),
+                    stats.to_line(1),
+                    "Part 2: ✖        ".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 1500_f64);
+            assert_eq!(res.part_1, Some(stats));
+            assert_eq!(res.part_2, None);
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use super::for_each_ordered;
+
+    #[test]
+    fn keeps_order_of_items() {
+        let mut results = vec![];
+        for_each_ordered(
+            vec![30, 0, 20, 10, 0],
+            3,
+            |millis| {
+                thread::sleep(Duration::from_millis(millis));
+                millis
+            },
+            |result| results.push(result),
+        );
+        assert_eq!(results, [30, 0, 20, 10, 0]);
+    }
+
+    #[test]
+    fn runs_without_items() {
+        let mut calls = 0;
+        for_each_ordered(Vec::<u8>::new(), 4, |x| x, |_| calls += 1);
+        assert_eq!(calls, 0);
     }
 }
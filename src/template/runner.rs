@@ -3,59 +3,129 @@ use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{Write, stdout};
 use std::process::Output;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{cmp, process};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli};
+use crate::template::answers::Answers;
+use crate::template::cli::SolutionArgs;
+use crate::template::examples::ExampleResult;
+use crate::template::report::{self, Status};
+use crate::template::timings::{PartTiming, format_bytes};
+use crate::template::variants::VariantResult;
+use crate::template::{ANSI_ITALIC, ANSI_RESET, Day, aoc_cli, readme_stars};
 
 pub fn run_part<I: Copy, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, timing) = run_timed(func, input, |result| print_result(result, &part_str, ""));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(
+        &result,
+        &part_str,
+        &format_duration(&timing.mean(), timing.samples),
+    );
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Some(memory) = &timing.memory {
+        println!(
+            "{part_str} memory: {} peak, {} allocations, {} allocated",
+            format_bytes(memory.peak_bytes),
+            memory.total_allocations,
+            format_bytes(memory.total_bytes)
+        );
+    }
+
+    // machine readable statistics for `cargo time`.
+    if SolutionArgs::get().stats {
+        println!("{}", timing.to_line(part));
+    }
+
+    check_answer(
+        result.as_ref().map(ToString::to_string).as_deref(),
+        day,
+        part,
+    );
+
+    if let Some(result) = result
+        && let Some(Ok(output)) = submit_result(&result, day, part)
+        && aoc_cli::is_accepted(&output)
+    {
+        record_answer(day, part, &result.to_string());
     }
 }
 
+/// Print example results in the line format understood by `cargo test-examples`.
+pub fn print_example_results(results: &[ExampleResult]) {
+    for result in results {
+        println!("{}", result.to_line());
+    }
+}
+
+/// Bench a variant of a part and print the result in the line format understood by `cargo compare`.
+pub fn run_variant<T: Display>(
+    func: impl Fn(&str) -> Option<T>,
+    input: &str,
+    part: u8,
+    name: &str,
+) {
+    let timer = Instant::now();
+    let answer = func(input);
+    let base_time = timer.elapsed();
+    let timing = bench(&func, input, &base_time);
+
+    let result = VariantResult {
+        part,
+        name: name.into(),
+        answer: answer.map(|answer| answer.to_string()),
+        duration: timing.mean(),
+        samples: u128::from(timing.samples),
+    };
+    println!("{}", result.to_line());
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Copy, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+fn run_timed<I: Copy, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T)) -> (T, PartTiming) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        let result = func(input);
+
+        #[cfg(feature = "dhat-heap")]
+        let memory = {
+            let stats = dhat::HeapStats::get();
+            Some(crate::template::timings::MemoryStats {
+                peak_bytes: stats.max_bytes as u64,
+                total_allocations: stats.total_blocks,
+                total_bytes: stats.total_bytes,
+            })
+        };
+        #[cfg(not(feature = "dhat-heap"))]
+        let memory = None;
+
+        (result, memory)
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
+    let mut timing = if SolutionArgs::get().time {
+        print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+        let _ = stdout().flush();
         bench(func, input, &base_time)
     } else {
-        (base_time, 1)
+        PartTiming::from_samples(&[base_time])
     };
+    timing.memory = memory;
 
-    (result, run.0, run.1)
+    (result, timing)
 }
 
-fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
-
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
-
+fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> PartTiming {
     let bench_iterations =
         (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
@@ -67,22 +137,10 @@ fn bench<I: Copy, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (
         timers.push(timer.elapsed());
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
-
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    PartTiming::from_samples(&timers)
 }
 
-fn format_duration(duration: &Duration, samples: u128) -> String {
+fn format_duration(duration: &Duration, samples: u64) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
     } else {
@@ -125,33 +183,74 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+/// Set once a part returned an answer that differs from the accepted one, or `None` although one was accepted.
+static HAS_FAILED: AtomicBool = AtomicBool::new(false);
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+/// Reports the answer of a part for the summary of `cargo all` and `cargo time`,
+/// and compares it to the accepted answer of the real input, if one was recorded.
+fn check_answer(answer: Option<&str>, day: Day, part: u8) {
+    let args = SolutionArgs::get();
+    if args.results {
+        println!("{}", report::to_line(part, answer));
     }
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
-    }
+    // accepted answers only apply to `data/inputs`.
+    let status = if args.input.is_none() {
+        let answers = Answers::read_from_file().unwrap_or_else(|err| {
+            eprintln!("warning: {err} Answers are not compared.");
+            Answers::default()
+        });
+        Status::check(&answers, day, part, answer)
+    } else {
+        Status::check(&Answers::default(), day, part, answer)
+    };
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+    if let Status::Mismatch { expected } = &status {
+        println!("Part {part}: ✖ differs from the accepted answer {expected}");
+    }
+    if status.is_failure() {
+        HAS_FAILED.store(true, Ordering::Relaxed);
+    }
+}
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
+/// Exits with a non-zero code if a part returned an answer that differs from the accepted one,
+/// so that scripts and CI can gate on the result of a solution.
+pub fn exit_on_failure() {
+    if HAS_FAILED.load(Ordering::Relaxed) {
         process::exit(1);
+    }
+}
+
+/// Stores an accepted answer and regenerates the stars table of the readme.
+fn record_answer(day: Day, part: u8, answer: &str) {
+    let mut answers = match Answers::read_from_file() {
+        Ok(answers) => answers,
+        Err(err) => {
+            eprintln!("Failed to store answer: {err} Fix or remove the file to record answers.");
+            return;
+        }
     };
+    answers.record(day, part, answer);
+    if let Err(err) = answers.store_file() {
+        eprintln!("Failed to store answer: {err}");
+        return;
+    }
+
+    match readme_stars::update(&answers) {
+        Ok(_) => println!("Updated stars table."),
+        Err(_) => eprintln!("Failed to update stars table."),
+    }
+}
 
-    if part_submit != part {
+/// Try to submit one part of the solution if:
+///  1. `--submit <part>` was passed for this part.
+///  2. aoc-cli is installed.
+fn submit_result<T: Display>(
+    result: &T,
+    day: Day,
+    part: u8,
+) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+    if SolutionArgs::get().submit != Some(part) {
         return None;
     }
 
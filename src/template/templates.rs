@@ -0,0 +1,193 @@
+/// Solution templates for `cargo scaffold`. Besides the built-in templates, user templates are read
+/// from `templates/<name>.txt`, or from the directory set in `AOC_TEMPLATE_DIR`.
+use std::{env, fs, path::PathBuf};
+
+use crate::template::Day;
+use crate::template::puzzle::Puzzle;
+
+pub const DEFAULT_TEMPLATE: &str = "default";
+
+const BUILTIN_TEMPLATES: &[(&str, &str)] = &[
+    (
+        DEFAULT_TEMPLATE,
+        include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt")),
+    ),
+    (
+        "graph",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/template/templates/graph.txt"
+        )),
+    ),
+    (
+        "grid",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/template/templates/grid.txt"
+        )),
+    ),
+    (
+        "parse-once",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/template/templates/parse_once.txt"
+        )),
+    ),
+    (
+        "string",
+        include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/template/templates/string.txt"
+        )),
+    ),
+];
+
+/// Names of the built-in templates, offered by shell completions.
+pub const BUILTIN_NAMES: [&str; BUILTIN_TEMPLATES.len()] = builtin_names();
+
+const fn builtin_names<const N: usize>() -> [&'static str; N] {
+    let mut names = [""; N];
+    let mut i = 0;
+    while i < N {
+        names[i] = BUILTIN_TEMPLATES[i].0;
+        i += 1;
+    }
+    names
+}
+
+#[must_use]
+pub fn get_template_dir() -> PathBuf {
+    env::var_os("AOC_TEMPLATE_DIR").map_or_else(|| PathBuf::from("templates"), PathBuf::from)
+}
+
+/// Names of all templates, built-in and user-defined, sorted.
+#[must_use]
+pub fn available() -> Vec<String> {
+    let mut names: Vec<String> = BUILTIN_TEMPLATES
+        .iter()
+        .map(|(name, _)| (*name).to_string())
+        .collect();
+
+    if let Ok(entries) = fs::read_dir(get_template_dir()) {
+        names.extend(
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter(|path| path.extension().is_some_and(|ext| ext == "txt"))
+                .filter_map(|path| Some(path.file_stem()?.to_str()?.to_string())),
+        );
+    }
+
+    names.sort_unstable();
+    names.dedup();
+    names
+}
+
+/// Loads a template by name. A user template takes precedence over a built-in template of the same name.
+///
+/// # Errors
+/// Returns a message for the user if the template does not exist or cannot be read.
+pub fn load(name: &str) -> Result<String, String> {
+    let is_valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if is_valid {
+        let path = get_template_dir().join(format!("{name}.txt"));
+        if path.is_file() {
+            return fs::read_to_string(&path)
+                .map_err(|err| format!("Could not read template \"{}\": {err}.", path.display()));
+        }
+        if let Some((_, template)) = BUILTIN_TEMPLATES.iter().find(|(n, _)| *n == name) {
+            return Ok((*template).to_string());
+        }
+    }
+
+    Err(format!(
+        "Unknown template \"{name}\". Available templates: {}.",
+        available().join(", ")
+    ))
+}
+
+/// Values for the placeholders of a template.
+#[derive(Debug)]
+pub struct Placeholders {
+    pub day: Day,
+    pub year: Option<u16>,
+    /// The downloaded puzzle description, which provides the title and the example answers.
+    pub puzzle: Puzzle,
+}
+
+impl Placeholders {
+    /// Collects the values for `day`. The puzzle is empty if it was not downloaded yet.
+    #[must_use]
+    pub fn new(day: Day, year: Option<u16>) -> Self {
+        Self {
+            day,
+            year,
+            puzzle: Puzzle::read(day).unwrap_or_default(),
+        }
+    }
+
+    /// Replaces the placeholders in `template`. Unknown values are replaced with an empty string.
+    #[must_use]
+    pub fn render(&self, template: &str) -> String {
+        let [part_1, part_2] = &self.puzzle.example_answers;
+        [
+            ("%DAY_NUMBER%", self.day.into_inner().to_string()),
+            ("%DAY%", self.day.to_string()),
+            (
+                "%YEAR%",
+                self.year.map(|year| year.to_string()).unwrap_or_default(),
+            ),
+            ("%TITLE%", self.puzzle.title.clone().unwrap_or_default()),
+            ("%EXAMPLE_PART_1%", part_1.clone().unwrap_or_default()),
+            ("%EXAMPLE_PART_2%", part_2.clone().unwrap_or_default()),
+        ]
+        .iter()
+        .fold(template.to_string(), |acc, (placeholder, value)| {
+            acc.replace(placeholder, value)
+        })
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BUILTIN_TEMPLATES, DEFAULT_TEMPLATE, Placeholders, available, load};
+    use crate::day;
+    use crate::template::puzzle::Puzzle;
+
+    #[test]
+    fn loads_builtin_templates() {
+        assert!(load(DEFAULT_TEMPLATE).unwrap().contains("%DAY_NUMBER%"));
+        assert!(load("grid").unwrap().contains("Map2D"));
+
+        let err = load("../template").unwrap_err();
+        assert!(err.starts_with("Unknown template \"../template\". Available templates: "));
+        for (name, template) in BUILTIN_TEMPLATES {
+            assert!(template.contains("solution!(%DAY_NUMBER%)"), "{name}");
+            assert!(available().iter().any(|n| n == name));
+            assert!(err.contains(name), "{name}");
+        }
+    }
+
+    #[test]
+    fn renders_placeholders() {
+        let placeholders = Placeholders {
+            day: day!(3),
+            year: Some(2025),
+            puzzle: Puzzle {
+                title: Some("Lobby".into()),
+                example_input: None,
+                example_answers: [Some("357".into()), None],
+            },
+        };
+        assert_eq!(
+            placeholders.render(
+                "// %YEAR% day %DAY%: %TITLE%\nsolution!(%DAY_NUMBER%);\n// %EXAMPLE_PART_1%, %EXAMPLE_PART_2%"
+            ),
+            "// 2025 day 03: Lobby\nsolution!(3);\n// 357, "
+        );
+    }
+}
@@ -1,17 +1,257 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Error, ErrorKind},
+    str::FromStr,
+    time::Duration,
+};
 use tinyjson::JsonValue;
 
 use crate::template::Day;
 
 static TIMINGS_FILE_PATH: &str = "./data/timings.json";
 
+/// Version of the JSON document written by [`Timings::store_file`].
+/// Version 1 documents, which stored parts as formatted strings, are migrated on read.
+pub const SCHEMA_VERSION: u8 = 2;
+
+static STATS_PREFIX: &str = "Timing stats:";
+
+/// Heap usage of a single run of a part, as reported by dhat.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Bytes allocated at the peak of heap usage.
+    pub peak_bytes: u64,
+    /// Number of allocations over the entire run.
+    pub total_allocations: u64,
+    /// Bytes allocated over the entire run.
+    pub total_bytes: u64,
+}
+
+impl MemoryStats {
+    /// Formats the peak heap usage and number of allocations, e.g. `1.5 KiB (12 allocs)`.
+    pub fn format(&self) -> String {
+        format!(
+            "{} ({} allocs)",
+            format_bytes(self.peak_bytes),
+            self.total_allocations
+        )
+    }
+}
+
+/// Formats a number of bytes with binary units, e.g. `1.5 KiB`.
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Sample statistics of a benchmarked part, in nanoseconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartTiming {
+    pub mean_nanos: f64,
+    pub median_nanos: f64,
+    pub min_nanos: f64,
+    pub max_nanos: f64,
+    pub std_dev_nanos: f64,
+    /// The number of samples. `0` if unknown, e.g. for timings migrated from version 1.
+    pub samples: u64,
+    /// Heap usage of the part, if it was profiled with `cargo time --dhat`.
+    pub memory: Option<MemoryStats>,
+}
+
+impl PartTiming {
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        let mut nanos: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        nanos.sort_unstable_by(f64::total_cmp);
+
+        let len = nanos.len().max(1) as f64;
+        let mean = nanos.iter().sum::<f64>() / len;
+        let variance = nanos.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / len;
+        let median = match nanos.len() {
+            0 => 0.0,
+            n if n % 2 == 0 => f64::midpoint(nanos[n / 2 - 1], nanos[n / 2]),
+            n => nanos[n / 2],
+        };
+
+        Self {
+            mean_nanos: mean,
+            median_nanos: median,
+            min_nanos: nanos.first().copied().unwrap_or_default(),
+            max_nanos: nanos.last().copied().unwrap_or_default(),
+            std_dev_nanos: variance.sqrt(),
+            samples: nanos.len() as u64,
+            memory: None,
+        }
+    }
+
+    /// A timing of which only the mean is known.
+    pub fn from_mean(mean_nanos: f64, samples: u64) -> Self {
+        Self {
+            mean_nanos,
+            median_nanos: mean_nanos,
+            min_nanos: mean_nanos,
+            max_nanos: mean_nanos,
+            std_dev_nanos: 0.0,
+            samples,
+            memory: None,
+        }
+    }
+
+    pub fn mean(&self) -> Duration {
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Duration::from_nanos(self.mean_nanos as u64)
+    }
+
+    /// Formats the mean like the runner does, e.g. `1.2ms`.
+    pub fn format(&self) -> String {
+        format!("{:.1?}", self.mean())
+    }
+
+    /// Serializes the statistics of `part` to a single line, so they can be passed from a solution binary to the runner.
+    pub fn to_line(&self, part: u8) -> String {
+        let mut line = format!(
+            "{STATS_PREFIX}\t{part}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.mean_nanos,
+            self.median_nanos,
+            self.min_nanos,
+            self.max_nanos,
+            self.std_dev_nanos,
+            self.samples
+        );
+        if let Some(memory) = &self.memory {
+            line += &format!(
+                "\t{}\t{}\t{}",
+                memory.peak_bytes, memory.total_allocations, memory.total_bytes
+            );
+        }
+        line
+    }
+
+    /// Parses a line written by [`PartTiming::to_line`] into the part and its statistics.
+    pub fn from_line(line: &str) -> Option<(u8, Self)> {
+        let mut fields = line
+            .strip_prefix(STATS_PREFIX)?
+            .strip_prefix('\t')?
+            .split('\t');
+        let part = fields.next()?.parse().ok()?;
+        let mut number = || fields.next()?.parse::<f64>().ok();
+
+        let mut timing = Self {
+            mean_nanos: number()?,
+            median_nanos: number()?,
+            min_nanos: number()?,
+            max_nanos: number()?,
+            std_dev_nanos: number()?,
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            samples: number()? as u64,
+            memory: None,
+        };
+
+        // memory statistics are only present for profiled runs.
+        let mut integer = || fields.next()?.parse::<u64>().ok();
+        if let (Some(peak_bytes), Some(total_allocations), Some(total_bytes)) =
+            (integer(), integer(), integer())
+        {
+            timing.memory = Some(MemoryStats {
+                peak_bytes,
+                total_allocations,
+                total_bytes,
+            });
+        }
+        Some((part, timing))
+    }
+}
+
+/// Parses a duration formatted by `Duration`'s `Debug` implementation, e.g. `74.13ms`, to nanoseconds.
+pub fn parse_duration(s: &str) -> Option<f64> {
+    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
+        s.split(postfix).next()?.parse().ok()
+    }
+
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    match s.trim() {
+        s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
+        s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
+        s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
+        s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
+    }
+}
+
+/// Why a solution run was stopped before finishing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Failure {
+    /// The run exceeded its timeout.
+    Timeout,
+    /// The resident memory of the run exceeded the memory limit.
+    MemoryLimit,
+}
+
+impl Failure {
+    fn as_str(self) -> &'static str {
+        match self {
+            Failure::Timeout => "timeout",
+            Failure::MemoryLimit => "memory_limit",
+        }
+    }
+}
+
+impl FromStr for Failure {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "timeout" => Ok(Failure::Timeout),
+            "memory_limit" => Ok(Failure::MemoryLimit),
+            x => Err(format!("unknown failure `{x}`")),
+        }
+    }
+}
+
+impl std::fmt::Display for Failure {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Failure::Timeout => write!(f, "timed out"),
+            Failure::MemoryLimit => write!(f, "out of memory"),
+        }
+    }
+}
+
+/// The part that was running when a solution run was stopped.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PartFailure {
+    pub part: u8,
+    pub failure: Failure,
+}
+
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub part_1: Option<PartTiming>,
+    pub part_2: Option<PartTiming>,
     pub total_nanos: f64,
+    /// Set if the run was stopped by a timeout or memory limit. Parts from this one on have no timing.
+    pub failure: Option<PartFailure>,
+}
+
+impl Timing {
+    /// The failure of `part`, if the run was stopped while it or an earlier part was running.
+    pub fn failure_of(&self, part: u8) -> Option<Failure> {
+        self.failure
+            .filter(|failure| failure.part <= part)
+            .map(|failure| failure.failure)
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -30,11 +270,14 @@ impl Timings {
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
-            .unwrap_or_default()
+    /// Fails if the file can not be read or parsed, so that callers do not overwrite stored timings.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(TIMINGS_FILE_PATH) {
+            Ok(contents) => Timings::try_from(contents),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(Timings::default()),
+            Err(err) => Err(err.to_string()),
+        }
+        .map_err(|err| format!("could not read {TIMINGS_FILE_PATH}: {err}"))
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
@@ -51,7 +294,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -60,6 +303,16 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
+    /// Whether any part has been profiled for memory usage.
+    pub fn has_memory_stats(&self) -> bool {
+        self.data.iter().any(|t| {
+            [&t.part_1, &t.part_2]
+                .into_iter()
+                .flatten()
+                .any(|p| p.memory.is_some())
+        })
+    }
+
     pub fn is_day_complete(&self, day: Day) -> bool {
         self.data
             .iter()
@@ -73,6 +326,10 @@ impl From<Timings> for JsonValue {
     fn from(value: Timings) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
+        map.insert(
+            "version".into(),
+            JsonValue::Number(f64::from(SCHEMA_VERSION)),
+        );
         map.insert(
             "data".into(),
             JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
@@ -88,18 +345,35 @@ impl TryFrom<String> for Timings {
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
 
-        let json_data = json
+        let json = json
             .get::<HashMap<String, JsonValue>>()
-            .ok_or("expected JSON document to be an object.")?
+            .ok_or("expected JSON document to be an object.")?;
+
+        // documents without a version were written before the schema was versioned.
+        let version = match json.get("version") {
+            None => 1.0,
+            Some(version) => version
+                .get::<f64>()
+                .copied()
+                .ok_or("expected `json.version` to be a number.")?,
+        };
+
+        let json_data = json
             .get("data")
             .ok_or("expected JSON document to have key `data`.")?
             .get::<Vec<JsonValue>>()
             .ok_or("expected `json.data` to be an array.")?;
 
+        let parse_timing = match version {
+            1.0 => Timing::try_from_v1,
+            2.0 => Timing::try_from_v2,
+            version => return Err(format!("unsupported timings version {version}.")),
+        };
+
         Ok(Timings {
             data: json_data
                 .iter()
-                .map(Timing::try_from)
+                .map(parse_timing)
                 .collect::<Result<_, _>>()?,
         })
     }
@@ -107,40 +381,136 @@ impl TryFrom<String> for Timings {
 
 /* -------------------------------------------------------------------------- */
 
-impl From<&Timing> for JsonValue {
-    fn from(value: &Timing) -> Self {
+impl From<&PartTiming> for JsonValue {
+    fn from(value: &PartTiming) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
-        map.insert("day".into(), JsonValue::String(value.day.to_string()));
-        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
+        map.insert("mean_nanos".into(), JsonValue::Number(value.mean_nanos));
+        map.insert("median_nanos".into(), JsonValue::Number(value.median_nanos));
+        map.insert("min_nanos".into(), JsonValue::Number(value.min_nanos));
+        map.insert("max_nanos".into(), JsonValue::Number(value.max_nanos));
+        map.insert(
+            "std_dev_nanos".into(),
+            JsonValue::Number(value.std_dev_nanos),
+        );
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+
+        if let Some(memory) = &value.memory {
+            map.insert("memory".into(), JsonValue::from(memory));
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for PartTiming {
+    type Error = String;
 
-        let part_1 = value.part_1.clone().map(JsonValue::String);
-        let part_2 = value.part_2.clone().map(JsonValue::String);
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected part timing to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected part timing.{key} to be a number."))
+        };
+
+        Ok(PartTiming {
+            mean_nanos: number("mean_nanos")?,
+            median_nanos: number("median_nanos")?,
+            min_nanos: number("min_nanos")?,
+            max_nanos: number("max_nanos")?,
+            std_dev_nanos: number("std_dev_nanos")?,
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            samples: number("samples")? as u64,
+            memory: json.get("memory").map(MemoryStats::try_from).transpose()?,
+        })
+    }
+}
+
+impl From<&MemoryStats> for JsonValue {
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
 
         map.insert(
-            "part_1".into(),
-            match part_1 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "peak_bytes".into(),
+            JsonValue::Number(value.peak_bytes as f64),
         );
-
         map.insert(
-            "part_2".into(),
-            match part_2 {
-                Some(x) => x,
-                None => JsonValue::Null,
-            },
+            "total_allocations".into(),
+            JsonValue::Number(value.total_allocations as f64),
+        );
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
         );
 
         JsonValue::Object(map)
     }
 }
 
-impl TryFrom<&JsonValue> for Timing {
+impl TryFrom<&JsonValue> for MemoryStats {
     type Error = String;
 
     fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory stats to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|v| v as u64)
+                .ok_or(format!("Expected memory.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            peak_bytes: number("peak_bytes")?,
+            total_allocations: number("total_allocations")?,
+            total_bytes: number("total_bytes")?,
+        })
+    }
+}
+
+impl From<&Timing> for JsonValue {
+    fn from(value: &Timing) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
+
+        let part_json =
+            |part: &Option<PartTiming>| part.as_ref().map_or(JsonValue::Null, JsonValue::from);
+        map.insert("part_1".into(), part_json(&value.part_1));
+        map.insert("part_2".into(), part_json(&value.part_2));
+
+        // only written for failed runs, so that documents of successful runs remain unchanged.
+        if let Some(failure) = value.failure {
+            map.insert(
+                "failure".into(),
+                JsonValue::Object(HashMap::from([
+                    ("part".into(), JsonValue::Number(f64::from(failure.part))),
+                    (
+                        "reason".into(),
+                        JsonValue::String(failure.failure.as_str().into()),
+                    ),
+                ])),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl Timing {
+    /// Parses the fields shared by all schema versions, using `parse_part` for the parts.
+    fn try_from_json(
+        value: &JsonValue,
+        parse_part: impl Fn(&JsonValue) -> Option<PartTiming>,
+    ) -> Result<Self, String> {
         let json = value
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
@@ -151,28 +521,63 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
-        let part_1 = json
-            .get("part_1")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_1 to be null or string.")?;
-
-        let part_2 = json
-            .get("part_2")
-            .map(|v| if v.is_null() { None } else { v.get::<String>() })
-            .ok_or("Expected timing.part_2 to be null or string.")?;
+        let part = |key: &str| {
+            let value = json
+                .get(key)
+                .ok_or(format!("Expected timing.{key} to be present."))?;
+            if value.is_null() {
+                return Ok(None);
+            }
+            parse_part(value).map(Some).ok_or(format!(
+                "Expected timing.{key} to be null or a part timing."
+            ))
+        };
 
         let total_nanos = json
             .get("total_nanos")
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        let failure = match json.get("failure") {
+            None => None,
+            Some(value) => {
+                let failure = value.get::<HashMap<String, JsonValue>>();
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let part = failure
+                    .and_then(|f| f.get("part"))
+                    .and_then(|v| v.get::<f64>())
+                    .map(|part| *part as u8);
+                let reason = failure
+                    .and_then(|f| f.get("reason"))
+                    .and_then(|v| v.get::<String>())
+                    .and_then(|reason| reason.parse().ok());
+                match (part, reason) {
+                    (Some(part), Some(failure)) => Some(PartFailure { part, failure }),
+                    _ => return Err("Expected timing.failure to have a part and reason.".into()),
+                }
+            }
+        };
+
         Ok(Timing {
             day,
-            part_1: part_1.cloned(),
-            part_2: part_2.cloned(),
+            part_1: part("part_1")?,
+            part_2: part("part_2")?,
             total_nanos,
+            failure,
         })
     }
+
+    /// Version 1 stored parts as formatted durations, e.g. `"74.13ns"`, without sample statistics.
+    fn try_from_v1(value: &JsonValue) -> Result<Self, String> {
+        Self::try_from_json(value, |part| {
+            let nanos = parse_duration(part.get::<String>()?)?;
+            Some(PartTiming::from_mean(nanos, 0))
+        })
+    }
+
+    fn try_from_v2(value: &JsonValue) -> Result<Self, String> {
+        Self::try_from_json(value, |part| PartTiming::try_from(part).ok())
+    }
 }
 
 /* -------------------------------------------------------------------------- */
@@ -181,46 +586,144 @@ impl TryFrom<&JsonValue> for Timing {
 mod tests {
     use crate::day;
 
-    use super::{Timing, Timings};
+    use super::{PartTiming, Timing, Timings};
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
-                    part_1: Some("10ms".into()),
-                    part_2: Some("20ms".into()),
+                    part_1: Some(PartTiming::from_mean(10e6, 10)),
+                    part_2: Some(PartTiming::from_mean(20e6, 10)),
                     total_nanos: 3e+10,
+                    failure: None,
                 },
                 Timing {
                     day: day!(2),
-                    part_1: Some("30ms".into()),
-                    part_2: Some("40ms".into()),
+                    part_1: Some(PartTiming::from_mean(30e6, 10)),
+                    part_2: Some(PartTiming::from_mean(40e6, 10)),
                     total_nanos: 7e+10,
+                    failure: None,
                 },
                 Timing {
                     day: day!(4),
-                    part_1: Some("40ms".into()),
+                    part_1: Some(PartTiming::from_mean(40e6, 10)),
                     part_2: None,
                     total_nanos: 4e+10,
+                    failure: None,
                 },
             ],
         }
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use std::time::Duration;
+
+        use super::get_mock_timings;
+        use crate::{
+            day,
+            template::timings::{
+                Failure, MemoryStats, PartFailure, PartTiming, Timings, format_bytes,
+            },
+        };
+        use tinyjson::JsonValue;
 
         #[test]
-        fn handles_json_timings() {
-            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+        fn migrates_version_1_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.5ms", "part_2": null, "total_nanos": 1500000 }] }"#.to_string();
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
             assert_eq!(timing.day, day!(1));
-            assert_eq!(timing.part_1, Some("1ms".to_string()));
+            assert_eq!(timing.part_1, Some(PartTiming::from_mean(1_500_000.0, 0)));
             assert_eq!(timing.part_2, None);
-            assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.total_nanos, 1_500_000_f64);
+        }
+
+        #[test]
+        fn handles_version_2_timings() {
+            let timings = get_mock_timings();
+            let json = JsonValue::from(timings.clone()).stringify().unwrap();
+            assert!(json.contains(r#""version":2"#));
+
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data.len(), timings.data.len());
+            for (parsed, timing) in parsed.data.iter().zip(&timings.data) {
+                assert_eq!(parsed.day, timing.day);
+                assert_eq!(parsed.part_1, timing.part_1);
+                assert_eq!(parsed.part_2, timing.part_2);
+            }
+        }
+
+        #[test]
+        fn handles_memory_stats() {
+            let mut timings = get_mock_timings();
+            let memory = MemoryStats {
+                peak_bytes: 2048,
+                total_allocations: 3,
+                total_bytes: 4096,
+            };
+            timings.data[0].part_2.as_mut().unwrap().memory = Some(memory.clone());
+            assert!(timings.has_memory_stats());
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].part_1.as_ref().unwrap().memory, None);
+            assert_eq!(
+                parsed.data[0].part_2.as_ref().unwrap().memory,
+                Some(memory.clone())
+            );
+
+            let mut timing = PartTiming::from_mean(10.0, 1);
+            timing.memory = Some(memory);
+            assert_eq!(PartTiming::from_line(&timing.to_line(1)), Some((1, timing)));
+        }
+
+        #[test]
+        fn handles_failures() {
+            let mut timings = get_mock_timings();
+            timings.data[2].failure = Some(PartFailure {
+                part: 2,
+                failure: Failure::Timeout,
+            });
+
+            let json = JsonValue::from(timings).stringify().unwrap();
+            assert_eq!(json.matches(r#""failure""#).count(), 1);
+
+            let parsed = Timings::try_from(json).unwrap();
+            assert_eq!(parsed.data[0].failure, None);
+            let timing = &parsed.data[2];
+            assert_eq!(timing.failure_of(1), None);
+            assert_eq!(timing.failure_of(2), Some(Failure::Timeout));
+
+            let json = r#"{ "version": 2, "data": [{ "day": "01", "part_1": null, "part_2": null, "total_nanos": 0, "failure": { "part": 1 } }] }"#;
+            assert!(Timings::try_from(json.to_string()).is_err());
+        }
+
+        #[test]
+        fn formats_bytes() {
+            assert_eq!(format_bytes(1023), "1023 B");
+            assert_eq!(format_bytes(1536), "1.5 KiB");
+            assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+        }
+
+        #[test]
+        fn rejects_unsupported_versions() {
+            let json = r#"{ "version": 3, "data": [] }"#.to_string();
+            assert!(Timings::try_from(json).is_err());
+        }
+
+        #[test]
+        fn computes_sample_statistics() {
+            let samples = [4, 1, 3, 2].map(Duration::from_nanos);
+            let timing = PartTiming::from_samples(&samples);
+            assert_eq!(timing.mean_nanos, 2.5);
+            assert_eq!(timing.median_nanos, 2.5);
+            assert_eq!(timing.min_nanos, 1.0);
+            assert_eq!(timing.max_nanos, 4.0);
+            assert!((timing.std_dev_nanos - 1.25_f64.sqrt()).abs() < 1e-9);
+            assert_eq!(timing.samples, 4);
+            assert_eq!(PartTiming::from_line(&timing.to_line(2)), Some((2, timing)));
         }
 
         #[test]
@@ -271,7 +774,7 @@ mod tests {
     mod is_day_complete {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{PartTiming, Timing, Timings},
         };
 
         #[test]
@@ -279,13 +782,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
-                    part_2: Some("2ms".into()),
+                    part_1: Some(PartTiming::from_mean(1e6, 10)),
+                    part_2: Some(PartTiming::from_mean(2e6, 10)),
                     total_nanos: 3_000_000_000_f64,
+                    failure: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +797,14 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
-                    part_1: Some("1ms".into()),
+                    part_1: Some(PartTiming::from_mean(1e6, 10)),
                     part_2: None,
                     total_nanos: 1_000_000_000_f64,
+                    failure: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -310,17 +815,18 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0.0,
+                    failure: None,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
-            template::timings::{Timing, Timings},
+            template::timings::{PartTiming, Timing, Timings},
         };
 
         use super::get_mock_timings;
@@ -334,6 +840,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    failure: None,
                 }],
             };
             let merged = timings.merge(&other);
@@ -354,6 +861,7 @@ mod tests {
                     part_1: None,
                     part_2: None,
                     total_nanos: 0_f64,
+                    failure: None,
                 }],
             };
             let merged = timings.merge(&other);
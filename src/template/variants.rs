@@ -0,0 +1,110 @@
+/// Alternative implementations of a part, registered with `solution!` and compared by `cargo compare`.
+use std::time::Duration;
+
+use crate::template::examples::{NONE_MARKER, escape, unescape};
+
+/// The answer and benchmark of one implementation of a part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VariantResult {
+    pub part: u8,
+    pub name: String,
+    pub answer: Option<String>,
+    pub duration: Duration,
+    pub samples: u128,
+}
+
+static RESULT_PREFIX: &str = "Variant result:";
+
+impl VariantResult {
+    /// Serializes the result to a single line, so it can be passed from a solution binary to the runner.
+    pub fn to_line(&self) -> String {
+        format!(
+            "{RESULT_PREFIX}\t{}\t{}\t{}\t{}\t{}",
+            self.part,
+            escape(&self.name),
+            self.answer
+                .as_deref()
+                .map_or_else(|| NONE_MARKER.into(), escape),
+            self.duration.as_nanos(),
+            self.samples
+        )
+    }
+
+    /// Parses a line written by [`VariantResult::to_line`]. Returns `None` for any other line.
+    pub fn from_line(line: &str) -> Option<Self> {
+        let mut fields = line
+            .strip_prefix(RESULT_PREFIX)?
+            .strip_prefix('\t')?
+            .split('\t');
+        let part = fields.next()?.parse().ok()?;
+        let name = unescape(fields.next()?);
+        let answer = fields.next()?;
+        let answer = (answer != NONE_MARKER).then(|| unescape(answer));
+        let nanos: u64 = fields.next()?.parse().ok()?;
+        let samples = fields.next()?.parse().ok()?;
+
+        Some(Self {
+            part,
+            name,
+            answer,
+            duration: Duration::from_nanos(nanos),
+            samples,
+        })
+    }
+}
+
+/// Groups `results` by part and checks that all variants of a part agree.
+/// Returns the parts whose variants returned different answers.
+pub fn mismatched_parts(results: &[VariantResult]) -> Vec<u8> {
+    let mut parts: Vec<u8> = results.iter().map(|r| r.part).collect();
+    parts.sort_unstable();
+    parts.dedup();
+
+    parts
+        .into_iter()
+        .filter(|&part| {
+            let mut answers = results.iter().filter(|r| r.part == part).map(|r| &r.answer);
+            let first = answers.next();
+            answers.any(|answer| Some(answer) != first)
+        })
+        .collect()
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{VariantResult, mismatched_parts};
+
+    fn result(part: u8, name: &str, answer: Option<&str>) -> VariantResult {
+        VariantResult {
+            part,
+            name: name.into(),
+            answer: answer.map(Into::into),
+            duration: Duration::from_nanos(1500),
+            samples: 10,
+        }
+    }
+
+    #[test]
+    fn serializes_results_to_lines() {
+        let variant = result(2, "string\tbased", Some("1\n2"));
+        assert_eq!(VariantResult::from_line(&variant.to_line()), Some(variant));
+
+        let variant = result(1, "part_one", None);
+        assert_eq!(VariantResult::from_line(&variant.to_line()), Some(variant));
+        assert_eq!(VariantResult::from_line("Part 1: 3"), None);
+    }
+
+    #[test]
+    fn finds_mismatched_parts() {
+        let results = [
+            result(1, "part_one", Some("3")),
+            result(2, "part_two", Some("5")),
+            result(2, "numeric", Some("5")),
+            result(2, "string", Some("6")),
+        ];
+        assert_eq!(mismatched_parts(&results), vec![2]);
+        assert_eq!(mismatched_parts(&results[..3]), Vec::<u8>::new());
+    }
+}
@@ -0,0 +1,174 @@
+/// Polling file watcher used by `cargo solve --watch`.
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime},
+};
+
+use crate::template::Day;
+
+/// How often the watched files are checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long the watched files have to stay unchanged before a change is reported.
+const DEBOUNCE: Duration = Duration::from_millis(500);
+
+/// A file or directory to watch.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WatchPath {
+    File(PathBuf),
+    /// All files in a directory, recursively.
+    Dir(PathBuf),
+    /// All entries of a directory whose name starts with the prefix, recursively.
+    Prefixed(PathBuf, String),
+}
+
+/// The modification time of every watched file. Comparing snapshots detects changed, added and removed files.
+pub type Snapshot = BTreeMap<PathBuf, SystemTime>;
+
+/// The paths that affect the solution of `day`.
+pub fn paths_for_day(day: Day) -> Vec<WatchPath> {
+    vec![
+        WatchPath::File(PathBuf::from(format!("src/bin/{day}.rs"))),
+        WatchPath::Dir(PathBuf::from("src/helper")),
+        WatchPath::Prefixed(PathBuf::from("data/inputs"), day.to_string()),
+        WatchPath::Prefixed(PathBuf::from("data/examples"), day.to_string()),
+    ]
+}
+
+fn add_file(snapshot: &mut Snapshot, path: &Path) {
+    if let Ok(modified) = fs::metadata(path).and_then(|m| m.modified()) {
+        snapshot.insert(path.to_path_buf(), modified);
+    }
+}
+
+fn add_dir(snapshot: &mut Snapshot, path: &Path, prefix: Option<&str>) {
+    let Ok(entries) = fs::read_dir(path) else {
+        return;
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if prefix.is_some_and(|prefix| !entry.file_name().to_string_lossy().starts_with(prefix)) {
+            continue;
+        }
+        if path.is_dir() {
+            add_dir(snapshot, &path, None);
+        } else {
+            add_file(snapshot, &path);
+        }
+    }
+}
+
+pub fn snapshot(paths: &[WatchPath]) -> Snapshot {
+    let mut snapshot = Snapshot::new();
+    for path in paths {
+        match path {
+            WatchPath::File(path) => add_file(&mut snapshot, path),
+            WatchPath::Dir(path) => add_dir(&mut snapshot, path, None),
+            WatchPath::Prefixed(path, prefix) => add_dir(&mut snapshot, path, Some(prefix)),
+        }
+    }
+    snapshot
+}
+
+/// Returns the files that differ between two snapshots.
+pub fn changed_files(before: &Snapshot, after: &Snapshot) -> Vec<PathBuf> {
+    let mut changed: Vec<PathBuf> = after
+        .iter()
+        .filter(|(path, modified)| before.get(*path) != Some(modified))
+        .map(|(path, _)| path.clone())
+        .chain(
+            before
+                .keys()
+                .filter(|path| !after.contains_key(*path))
+                .cloned(),
+        )
+        .collect();
+    changed.sort_unstable();
+    changed
+}
+
+/// Blocks until a watched file changes and no further change happened for the debounce period.
+/// Returns the new snapshot and the files that changed.
+pub fn wait_for_change(paths: &[WatchPath], current: &Snapshot) -> (Snapshot, Vec<PathBuf>) {
+    loop {
+        thread::sleep(POLL_INTERVAL);
+        let mut next = snapshot(paths);
+        if next == *current {
+            continue;
+        }
+
+        // wait for a burst of saves to settle.
+        loop {
+            thread::sleep(DEBOUNCE);
+            let settled = snapshot(paths);
+            if settled == next {
+                break;
+            }
+            next = settled;
+        }
+
+        let changed = changed_files(current, &next);
+        return (next, changed);
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{fs, path::PathBuf, time::SystemTime};
+
+    use super::{Snapshot, WatchPath, changed_files, snapshot};
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("aoc-watch-{name}-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn snapshots_prefixed_entries() {
+        let dir = temp_dir("prefixed");
+        fs::write(dir.join("01.txt"), "a").unwrap();
+        fs::write(dir.join("02.txt"), "b").unwrap();
+        fs::create_dir_all(dir.join("01")).unwrap();
+        fs::write(dir.join("01").join("example.txt"), "c").unwrap();
+
+        let files: Vec<PathBuf> = snapshot(&[WatchPath::Prefixed(dir.clone(), "01".into())])
+            .into_keys()
+            .collect();
+        assert_eq!(
+            files,
+            [dir.join("01").join("example.txt"), dir.join("01.txt")]
+        );
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn finds_changed_files() {
+        let time = |secs| SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(secs);
+        let before = Snapshot::from([
+            ("a".into(), time(1)),
+            ("b".into(), time(1)),
+            ("c".into(), time(1)),
+        ]);
+        let after = Snapshot::from([
+            ("a".into(), time(1)),
+            ("b".into(), time(2)),
+            ("d".into(), time(1)),
+        ]);
+
+        let changed: Vec<PathBuf> = ["b", "c", "d"].map(PathBuf::from).into();
+        assert_eq!(changed_files(&before, &after), changed);
+    }
+
+    #[test]
+    fn ignores_missing_paths() {
+        let missing = std::env::temp_dir().join("aoc-watch-missing");
+        assert!(snapshot(&[WatchPath::File(missing.clone()), WatchPath::Dir(missing)]).is_empty());
+    }
+}